@@ -1,5 +1,5 @@
 #!/bin/sh -e
-wasm-pack build --dev --target web --out-name wasm --out-dir ./public
+wasm-pack build --dev --target web --out-name wasm --out-dir ./public -- --no-default-features --features web
 cp ./static/* ./public/
 miniserve ./public --index index.html
 
@@ -0,0 +1,152 @@
+//! WebAssembly component of passgen, which exports `generator` interface of `wit/passgen.wit`.
+// export names such as `hayas1:passgen/generator@1.0.0#generate` cannot be linked natively
+#![cfg(target_family = "wasm")]
+
+wit_bindgen::generate!({
+    world: "passgen",
+    path: "wit",
+});
+
+use std::convert::TryFrom;
+
+use exports::hayas1::passgen::generator::{
+    ErrorKind, GeneratorError, Guest, KeyboardLayout, KeyboardSetting, LengthUnit, Settings,
+    UnicodeClass,
+};
+use passgen_core::{
+    filter::WordFilter, generator::PasswordGenerator, keyboard, password, password::Password,
+    symbol, symbol::MarkSet, PasswordError,
+};
+
+/// component, which implements exported `generator` interface
+pub struct Component;
+
+export!(Component);
+
+impl Guest for Component {
+    fn default_settings() -> Settings {
+        Settings::from(&PasswordGenerator::default())
+    }
+
+    fn can_generate(settings: Settings) -> Result<(), GeneratorError> {
+        let generator = PasswordGenerator::try_from(settings)?;
+        generator.can_generate().map_err(GeneratorError::from)
+    }
+
+    fn generate(settings: Settings) -> Result<String, GeneratorError> {
+        let generator = PasswordGenerator::try_from(settings)?;
+        let password = generator.generate_password().map_err(GeneratorError::from)?;
+        Ok(password.to_string())
+    }
+
+    fn validate(settings: Settings, password: String) -> Result<bool, GeneratorError> {
+        let generator = PasswordGenerator::try_from(settings)?;
+        let chars: Vec<char> = password.chars().collect();
+        let password = Password::from_chars(&chars).map_err(GeneratorError::from)?;
+        Ok(generator.validate(&password))
+    }
+}
+
+impl From<&PasswordGenerator> for Settings {
+    fn from(generator: &PasswordGenerator) -> Self {
+        Settings {
+            len: generator.len as u32,
+            use_lower: generator.use_lower,
+            use_upper: generator.use_upper,
+            use_numeric: generator.use_numeric,
+            mark: generator.mark.iter().collect(),
+            addition: generator.addition.iter().collect(),
+            filter: !generator.filter.is_empty(),
+            words: Vec::new(),
+            context: generator.context.clone(),
+            keyboard: generator.keyboard.as_ref().map(|keyboard| KeyboardSetting {
+                layout: keyboard.layout.into(),
+                max_switches: keyboard.max_switches.map(|max| max as u32),
+                min_entropy: keyboard.min_entropy,
+            }),
+            unicode: generator.unicode.iter().map(|&class| class.into()).collect(),
+            unit: generator.unit.into(),
+        }
+    }
+}
+
+impl TryFrom<Settings> for PasswordGenerator {
+    type Error = GeneratorError;
+    fn try_from(settings: Settings) -> Result<Self, Self::Error> {
+        let mut mark = MarkSet::new();
+        for c in settings.mark.chars() {
+            if !mark.contains(&c) && !mark.insert(c) {
+                return Err(GeneratorError {
+                    kind: ErrorKind::InvalidMark,
+                    message: format!("{:?} cannot be used as mark", c),
+                });
+            }
+        }
+        let mut filter = if settings.filter { WordFilter::bundled() } else { WordFilter::new() };
+        for word in settings.words.iter() {
+            filter.insert(word);
+        }
+        Ok(PasswordGenerator {
+            len: settings.len as usize,
+            use_lower: settings.use_lower,
+            use_upper: settings.use_upper,
+            use_numeric: settings.use_numeric,
+            mark,
+            addition: symbol::normalized_chars(&settings.addition),
+            filter,
+            context: settings.context,
+            keyboard: settings.keyboard.map(|keyboard| keyboard::KeyboardSetting {
+                layout: keyboard.layout.into(),
+                max_switches: keyboard.max_switches.map(|max| max as usize),
+                min_entropy: keyboard.min_entropy,
+            }),
+            unicode: settings.unicode.into_iter().map(Into::into).collect(),
+            unit: settings.unit.into(),
+        })
+    }
+}
+
+impl From<anyhow::Error> for GeneratorError {
+    fn from(err: anyhow::Error) -> Self {
+        let kind = match err.downcast_ref::<passgen_core::GeneratorError>() {
+            Some(passgen_core::GeneratorError::EmptyLength) => ErrorKind::EmptyLength,
+            Some(passgen_core::GeneratorError::TooShortLength(_)) => ErrorKind::TooShortLength,
+            Some(passgen_core::GeneratorError::TooLongLength(_)) => ErrorKind::TooLongLength,
+            Some(passgen_core::GeneratorError::EmptySymbol) => ErrorKind::EmptySymbol,
+            Some(passgen_core::GeneratorError::TooManyRedraw(_)) => ErrorKind::TooManyRedraw,
+            Some(passgen_core::GeneratorError::LowEntropy(_, _)) => ErrorKind::LowEntropy,
+            Some(passgen_core::GeneratorError::TooFewSwitches(_, _)) => ErrorKind::TooFewSwitches,
+            Some(passgen_core::GeneratorError::InvalidChar(_)) => ErrorKind::InvalidChar,
+            Some(passgen_core::GeneratorError::KeyboardWithBytes) => ErrorKind::KeyboardWithBytes,
+            Some(passgen_core::GeneratorError::TooManyClasses(_, _)) => ErrorKind::TooManyClasses,
+            // password longer than buffer
+            None if err.is::<PasswordError>() => ErrorKind::TooLongLength,
+            None => ErrorKind::Unknown,
+        };
+        GeneratorError { kind, message: err.to_string() }
+    }
+}
+
+macro_rules! convert_enum {
+    ($wit:ident, $core:path, [$($variant:ident),* $(,)?]) => {
+        impl From<$wit> for $core {
+            fn from(value: $wit) -> Self {
+                match value {
+                    $($wit::$variant => <$core>::$variant,)*
+                }
+            }
+        }
+
+        impl From<$core> for $wit {
+            fn from(value: $core) -> Self {
+                match value {
+                    $(<$core>::$variant => $wit::$variant,)*
+                }
+            }
+        }
+    };
+}
+
+convert_enum!(LengthUnit, password::LengthUnit, [Chars, Graphemes, Bytes]);
+convert_enum!(UnicodeClass, symbol::UnicodeClass, [Latin1, Greek, Cyrillic, Kana, Emoji]);
+convert_enum!(KeyboardLayout, keyboard::KeyboardLayout, [Us, Jis, Azerty, Qwertz, Ios, Android]);
@@ -0,0 +1,109 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+use wasmtime::{
+    component::{self, Component, ResourceTable},
+    Engine, Linker, Module, Store,
+};
+use wasmtime_wasi::{p2::pipe::MemoryOutputPipe, I32Exit, WasiCtx, WasiCtxView, WasiView};
+
+wasmtime::component::bindgen!({
+    world: "passgen",
+    path: "wit",
+});
+
+use exports::hayas1::passgen::generator::{ErrorKind, KeyboardLayout, KeyboardSetting};
+
+/// build wasm of the crate for the target, and return its path
+fn build_wasm(manifest: &str, target: &str, args: &[&str], name: &str) -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // separated target directory, to avoid waiting for the lock of running cargo test
+    let target_dir = manifest_dir.join("../target/wasi");
+    let output = Command::new(env!("CARGO"))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join(manifest))
+        .args(["--target", target])
+        .args(args)
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    target_dir.join(target).join("debug").join(name)
+}
+
+struct State {
+    ctx: WasiCtx,
+    table: ResourceTable,
+}
+
+impl WasiView for State {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView { ctx: &mut self.ctx, table: &mut self.table }
+    }
+}
+
+#[test]
+fn component_test() {
+    let path = build_wasm("Cargo.toml", "wasm32-wasip2", &[], "passgen_component.wasm");
+    let engine = Engine::default();
+    let component = Component::from_file(&engine, path).unwrap();
+    let mut linker = component::Linker::new(&engine);
+    wasmtime_wasi::p2::add_to_linker_sync(&mut linker).unwrap();
+    let state = State { ctx: WasiCtx::builder().build(), table: ResourceTable::new() };
+    let mut store = Store::new(&engine, state);
+    let passgen = Passgen::instantiate(&mut store, &component, &linker).unwrap();
+    let generator = passgen.hayas1_passgen_generator();
+
+    let mut settings = generator.call_default_settings(&mut store).unwrap();
+    assert_eq!(settings.len, 20);
+    assert!(settings.use_lower && settings.use_upper && settings.use_numeric);
+    assert!(generator.call_can_generate(&mut store, &settings).unwrap().is_ok());
+    let password = generator.call_generate(&mut store, &settings).unwrap().unwrap();
+    assert_eq!(password.chars().count(), 20);
+    assert!(generator.call_validate(&mut store, &settings, &password).unwrap().unwrap());
+
+    settings.len = 32;
+    settings.use_upper = false;
+    settings.mark = "-_".to_string();
+    settings.keyboard = Some(KeyboardSetting {
+        layout: KeyboardLayout::Ios,
+        max_switches: None,
+        min_entropy: 64.0,
+    });
+    let password = generator.call_generate(&mut store, &settings).unwrap().unwrap();
+    assert_eq!(password.chars().count(), 32);
+    assert!(password.chars().all(|c| !c.is_ascii_uppercase()));
+    assert!(!generator.call_validate(&mut store, &settings, "abc").unwrap().unwrap());
+
+    settings.len = 4;
+    let error = generator.call_generate(&mut store, &settings).unwrap().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::TooShortLength);
+    settings.mark = "a".to_string();
+    let error = generator.call_can_generate(&mut store, &settings).unwrap().unwrap_err();
+    assert_eq!(error.kind, ErrorKind::InvalidMark);
+}
+
+#[test]
+fn cli_test() {
+    let args = ["--no-default-features", "--features", "cli", "--bin", "passgen"];
+    let path = build_wasm("../Cargo.toml", "wasm32-wasip1", &args, "passgen.wasm");
+    let engine = Engine::default();
+    let module = Module::from_file(&engine, path).unwrap();
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::p1::add_to_linker_sync(&mut linker, |ctx| ctx).unwrap();
+    let stdout = MemoryOutputPipe::new(1024);
+    let ctx = WasiCtx::builder().args(&["passgen", "-m", "32"]).stdout(stdout.clone()).build_p1();
+    let mut store = Store::new(&engine, ctx);
+    let instance = linker.instantiate(&mut store, &module).unwrap();
+    let start = instance.get_typed_func::<(), ()>(&mut store, "_start").unwrap();
+    // main exits by `std::process::exit`
+    let error = start.call(&mut store, ()).unwrap_err();
+    assert_eq!(error.downcast_ref::<I32Exit>().map(|exit| exit.0), Some(0));
+
+    let output = String::from_utf8(stdout.contents().to_vec()).unwrap();
+    let password = output.trim_end();
+    assert_eq!(password.chars().count(), 32);
+    assert!(password.chars().all(|c| c.is_ascii_alphanumeric()));
+}
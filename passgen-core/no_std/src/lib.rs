@@ -0,0 +1,17 @@
+//! This crate defines its own panic handler, which conflicts with the one of std.
+//! So it can be compiled only if passgen-core and all of its dependencies are no_std.
+#![no_std]
+
+use core::panic::PanicInfo;
+use passgen_core::{Password, PasswordGenerator};
+use rand_core::{CryptoRng, RngCore};
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
+
+/// generate password with CSPRNG supplied by caller, such as hardware RNG of firmware
+pub fn generate<R: RngCore + CryptoRng>(csp_rng: &mut R) -> Option<Password> {
+    PasswordGenerator::default().generate_password_with_rng(csp_rng).ok()
+}
@@ -0,0 +1,304 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    password::{fill_volatile, Password},
+    token::Encoding,
+    ParseError, TokenError, PASSWORD_FILL_CHARACTER,
+};
+
+/// default length of random body, which is about 178 bits in base62
+pub const API_TOKEN_DEFAULT_BODY_LENGTH: usize = 30;
+
+/// min entropy bits of random body, shorter body is easy to guess even with prefix
+pub const API_TOKEN_MIN_BODY_ENTROPY: f64 = 128.0;
+
+/// checksum suffix of api token, which can be verified offline by secret scanners
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Checksum {
+    /// CRC32 in base62, left padded with zero to 6 characters, as GitHub tokens
+    #[default]
+    Crc32Base62,
+    /// CRC32 in lower case hex, 8 characters
+    Crc32Hex,
+    None,
+}
+
+impl FromStr for Checksum {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|checksum| checksum.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownChecksum(s.to_string()))
+    }
+}
+
+impl Checksum {
+    pub const ALL: [Checksum; 3] = [Checksum::Crc32Base62, Checksum::Crc32Hex, Checksum::None];
+
+    /// return name of this checksum, such as "crc32-base62"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Checksum::Crc32Base62 => "crc32-base62",
+            Checksum::Crc32Hex => "crc32-hex",
+            Checksum::None => "none",
+        }
+    }
+
+    /// return the number of characters of checksum
+    pub fn len(&self) -> usize {
+        match self {
+            Checksum::Crc32Base62 => 6,
+            Checksum::Crc32Hex => 8,
+            Checksum::None => 0,
+        }
+    }
+
+    /// return true if this is no checksum
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// return checksum of given characters, which are prefix and body
+    pub fn checksum<'a>(&self, chars: impl IntoIterator<Item = &'a char>) -> Vec<char> {
+        let mut crc = Crc32::new();
+        for c in chars {
+            crc.update(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+        let (radix, alphabet) = match self {
+            Checksum::Crc32Base62 => (62, Encoding::Base62.alphabet().as_bytes()),
+            Checksum::Crc32Hex => (16, Encoding::Hex.alphabet().as_bytes()),
+            Checksum::None => return Vec::new(),
+        };
+        let mut value = crc.finalize();
+        let mut checksum = Vec::with_capacity(self.len());
+        for _ in 0..self.len() {
+            checksum.push(alphabet[(value % radix) as usize] as char);
+            value /= radix;
+        }
+        checksum.reverse();
+        checksum
+    }
+}
+
+/// CRC-32 (IEEE 802.3), as zlib and PNG
+#[derive(Debug, Clone, Copy)]
+pub struct Crc32 {
+    crc: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc32 {
+    /// reversed polynomial of CRC-32
+    pub const POLYNOMIAL: u32 = 0xedb88320;
+
+    pub fn new() -> Self {
+        Self { crc: !0 }
+    }
+
+    pub fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.crc ^= byte as u32;
+            for _ in 0..8 {
+                let mask = (self.crc & 1).wrapping_neg();
+                self.crc = (self.crc >> 1) ^ (Self::POLYNOMIAL & mask);
+            }
+        }
+    }
+
+    pub fn finalize(&self) -> u32 {
+        !self.crc
+    }
+}
+
+/// format of api token, that is identifiable prefix, random body and checksum suffix
+/// such as `acme_live_` + `Wq8Bz...` + `0k3XyZ`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiTokenSpec {
+    pub prefix: String,
+    pub alphabet: Encoding,
+    pub body_len: usize,
+    pub checksum: Checksum,
+}
+
+impl Default for ApiTokenSpec {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            alphabet: Encoding::Base62,
+            body_len: API_TOKEN_DEFAULT_BODY_LENGTH,
+            checksum: Checksum::default(),
+        }
+    }
+}
+
+impl ApiTokenSpec {
+    pub fn new(prefix: &str, alphabet: Encoding, body_len: usize, checksum: Checksum) -> Self {
+        Self { prefix: prefix.to_string(), alphabet, body_len, checksum }
+    }
+
+    /// return the number of characters of token, including prefix and checksum
+    pub fn get_len(&self) -> usize {
+        self.prefix.chars().count() + self.body_len + self.checksum.len()
+    }
+
+    /// return entropy bits of random body
+    pub fn entropy(&self) -> f64 {
+        self.body_len as f64 * self.alphabet.bits_per_char()
+    }
+
+    /// return Ok if token can be generated, or its error
+    pub fn can_generate(&self) -> anyhow::Result<()> {
+        if self.entropy() < API_TOKEN_MIN_BODY_ENTROPY {
+            Err(TokenError::LowBodyEntropy(self.entropy()))?
+        }
+        Ok(())
+    }
+
+    /// generate api token with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate_token(&self) -> anyhow::Result<Password> {
+        self.generate_token_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate api token with given CSPRNG, each character of body is drawn uniformly
+    pub fn generate_token_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        self.can_generate()?;
+        let alphabet: Vec<char> = self.alphabet.alphabet().chars().collect();
+        let body = Password::generate_with_rng(self.body_len, &alphabet, csp_rng)?;
+        let mut chars: Vec<char> = self.prefix.chars().chain(body.iter().copied()).collect();
+        chars.extend(self.checksum.checksum(&chars));
+        let token = Password::from_chars(&chars);
+        fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+        token
+    }
+
+    /// check that token has prefix, body of alphabet and length, and correct checksum.
+    /// this is offline check before database lookup, so it does not tell the token is issued
+    pub fn verify_token(&self, token: &str) -> anyhow::Result<()> {
+        let rest = token.strip_prefix(&self.prefix[..]).ok_or(TokenError::InvalidPrefix)?;
+        let rest: Vec<char> = rest.chars().collect();
+        if rest.len() != self.body_len + self.checksum.len() {
+            Err(TokenError::InvalidLength(self.get_len(), token.chars().count()))?
+        }
+        let (body, checksum) = rest.split_at(self.body_len);
+        if let Some(&c) = body.iter().find(|&&c| !self.alphabet.alphabet().contains(c)) {
+            Err(TokenError::InvalidChar(c))?
+        }
+        let chars: Vec<char> = self.prefix.chars().chain(body.iter().copied()).collect();
+        let expected = self.checksum.checksum(&chars);
+        if checksum != &expected[..] {
+            Err(TokenError::InvalidChecksum)?
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn crc32_test() {
+        let mut crc = Crc32::new();
+        crc.update(b"123456789");
+        assert_eq!(crc.finalize(), 0xcbf43926);
+        let mut crc = Crc32::new();
+        crc.update(b"The quick brown fox ");
+        crc.update(b"jumps over the lazy dog");
+        assert_eq!(crc.finalize(), 0x414fa339);
+        assert_eq!(Crc32::new().finalize(), 0);
+    }
+
+    #[test]
+    fn checksum_test() {
+        let chars: Vec<_> = "123456789".chars().collect();
+        let hex: String = Checksum::Crc32Hex.checksum(&chars).into_iter().collect();
+        assert_eq!(hex, "cbf43926");
+        // 0xcbf43926 is 3421780262, which is "3jZRME" in base62
+        let base62: String = Checksum::Crc32Base62.checksum(&chars).into_iter().collect();
+        assert_eq!(base62, "3jZRME");
+        assert!(Checksum::None.checksum(&chars).is_empty());
+        // left padded with zero
+        let base62: String = Checksum::Crc32Base62.checksum(&[]).into_iter().collect();
+        assert_eq!(base62, "000000");
+    }
+
+    #[test]
+    fn generate_test() {
+        let spec = ApiTokenSpec::new("acme_live_", Encoding::Base62, 30, Checksum::Crc32Base62);
+        let token = spec.generate_token().unwrap().to_string();
+        assert!(token.starts_with("acme_live_"));
+        assert_eq!(token.chars().count(), 46);
+        assert_eq!(spec.get_len(), 46);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'));
+        spec.verify_token(&token).unwrap();
+
+        let spec = ApiTokenSpec::new("", Encoding::Crockford, 40, Checksum::Crc32Hex);
+        let token = spec.generate_token().unwrap().to_string();
+        assert_eq!(token.chars().count(), 48);
+        spec.verify_token(&token).unwrap();
+    }
+
+    #[test]
+    fn generate_with_rng_test() {
+        let spec = ApiTokenSpec::default();
+        let a = spec.generate_token_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        let b = spec.generate_token_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        assert_eq!(a.to_string(), b.to_string());
+        assert_eq!(a.len(), 36);
+    }
+
+    #[test]
+    fn verify_test() {
+        let spec = ApiTokenSpec::new("acme_", Encoding::Base62, 22, Checksum::Crc32Base62);
+        let token = spec.generate_token().unwrap().to_string();
+        spec.verify_token(&token).unwrap();
+
+        let error = spec.verify_token(&token.replacen("acme_", "acne_", 1)).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(TokenError::InvalidPrefix)));
+        let error = spec.verify_token(&token[..token.len() - 1]).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(TokenError::InvalidLength(33, 32))));
+        let error = spec.verify_token(&format!("acme_-{}", &token[6..])).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(TokenError::InvalidChar('-'))));
+
+        // one character typo in body is detected by checksum
+        let mut chars: Vec<char> = token.chars().collect();
+        chars[10] = if chars[10] == 'a' { 'b' } else { 'a' };
+        let typo: String = chars.into_iter().collect();
+        let error = spec.verify_token(&typo).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(TokenError::InvalidChecksum)));
+    }
+
+    #[test]
+    fn low_entropy_test() {
+        let spec = ApiTokenSpec::new("acme_", Encoding::Hex, 16, Checksum::None);
+        assert!(spec.generate_token().is_err());
+        let spec = ApiTokenSpec::new("acme_", Encoding::Hex, 32, Checksum::None);
+        assert_eq!(spec.generate_token().unwrap().len(), 37);
+    }
+
+    #[test]
+    fn parse_checksum_test() {
+        for checksum in Checksum::ALL.iter() {
+            assert_eq!(checksum.name().parse::<Checksum>().unwrap(), *checksum);
+        }
+        assert!("crc64".parse::<Checksum>().is_err());
+    }
+}
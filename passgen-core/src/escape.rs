@@ -0,0 +1,168 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{fmt::Write, str::FromStr};
+
+use super::ParseError;
+
+/// target context of password, such as shell script or json
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Escape {
+    Shell,
+    Json,
+    Yaml,
+    /// ANSI SQL string literal, which is for PostgreSQL, SQLite, SQL Server and Oracle
+    Sql,
+    /// MySQL default mode treats backslash as escape, so it is doubled as well as quote
+    SqlMysql,
+    Url,
+    Xml,
+}
+
+impl FromStr for Escape {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|escape| escape.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownEscape(s.to_string()))
+    }
+}
+
+impl Escape {
+    pub const ALL: [Escape; 7] = [
+        Escape::Shell,
+        Escape::Json,
+        Escape::Yaml,
+        Escape::Sql,
+        Escape::SqlMysql,
+        Escape::Url,
+        Escape::Xml,
+    ];
+
+    /// return name of this escape, such as "shell"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Escape::Shell => "shell",
+            Escape::Json => "json",
+            Escape::Yaml => "yaml",
+            Escape::Sql => "sql",
+            Escape::SqlMysql => "sql-mysql",
+            Escape::Url => "url",
+            Escape::Xml => "xml",
+        }
+    }
+
+    /// quote or escape given string, then it can be used as literal in target context
+    pub fn escape(&self, s: &str) -> String {
+        match self {
+            Escape::Shell => format!("'{}'", s.replace('\'', "'\\''")),
+            Escape::Json => Self::escape_json(s),
+            Escape::Yaml => format!("'{}'", s.replace('\'', "''")),
+            Escape::Sql => format!("'{}'", s.replace('\'', "''")),
+            Escape::SqlMysql => format!("'{}'", s.replace('\'', "''").replace('\\', "\\\\")),
+            Escape::Url => Self::escape_url(s),
+            Escape::Xml => Self::escape_xml(s),
+        }
+    }
+
+    fn escape_json(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len() + 2);
+        escaped.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                c if c.is_control() => write!(escaped, "\\u{:04x}", c as u32).expect("infallible"),
+                c => escaped.push(c),
+            }
+        }
+        escaped.push('"');
+        escaped
+    }
+
+    fn escape_url(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for b in s.bytes() {
+            match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    escaped.push(b as char)
+                }
+                b => write!(escaped, "%{:02X}", b).expect("infallible"),
+            }
+        }
+        escaped
+    }
+
+    fn escape_xml(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                c => escaped.push(c),
+            }
+        }
+        escaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_test() {
+        assert_eq!("shell".parse::<Escape>().unwrap(), Escape::Shell);
+        assert_eq!("JSON".parse::<Escape>().unwrap(), Escape::Json);
+        for escape in Escape::ALL.iter() {
+            assert_eq!(escape.name().parse::<Escape>().unwrap(), *escape);
+        }
+        assert_eq!("csv".parse::<Escape>().unwrap_err().to_string(), "unknown escape: csv");
+    }
+
+    #[test]
+    fn escape_shell_test() {
+        assert_eq!(Escape::Shell.escape("a$b"), "'a$b'");
+        assert_eq!(Escape::Shell.escape("a'b"), "'a'\\''b'");
+    }
+
+    #[test]
+    fn escape_json_test() {
+        assert_eq!(Escape::Json.escape("a\"b\\c"), "\"a\\\"b\\\\c\"");
+        assert_eq!(Escape::Json.escape("a\u{1}"), "\"a\\u0001\"");
+    }
+
+    #[test]
+    fn escape_yaml_sql_test() {
+        assert_eq!(Escape::Yaml.escape("a'b: #c"), "'a''b: #c'");
+        assert_eq!(Escape::Sql.escape("a';--"), "'a'';--'");
+        assert_eq!(Escape::Sql.escape("a\\"), "'a\\'");
+        assert_eq!(Escape::SqlMysql.escape("a';--"), "'a'';--'");
+        assert_eq!(Escape::SqlMysql.escape("a\\"), "'a\\\\'");
+        assert_eq!(Escape::SqlMysql.escape("\\'"), "'\\\\'''");
+    }
+
+    #[test]
+    fn escape_url_test() {
+        assert_eq!(Escape::Url.escape("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(Escape::Url.escape("a/b?c=d&e%"), "a%2Fb%3Fc%3Dd%26e%25");
+        assert_eq!(Escape::Url.escape("é"), "%C3%A9");
+    }
+
+    #[test]
+    fn escape_xml_test() {
+        assert_eq!(
+            Escape::Xml.escape("<a href=\"x\">'&'</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;"
+        );
+    }
+}
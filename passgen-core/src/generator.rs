@@ -0,0 +1,871 @@
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use core::fmt;
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    filter::WordFilter,
+    keyboard::{KeyboardLayout, KeyboardSetting, SwitchSampler, KEYBOARD_LAYER_COUNT},
+    password::{LengthUnit, Password},
+    symbol::{self, MarkSet, UnicodeClass},
+    GeneratorError, CONTEXT_MIN_TOKEN_LENGTH, PASSWORD_DEFAULT_LENGTH, PASSWORD_MAX_INVALID_DRAW,
+    PASSWORD_MAX_LENGTH, PASSWORD_MAX_REDRAW, PASSWORD_MIN_LENGTH,
+};
+
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct PasswordGenerator {
+    pub len: usize,
+    pub use_lower: bool,
+    pub use_upper: bool,
+    pub use_numeric: bool,
+    pub mark: MarkSet,
+    #[cfg_attr(feature = "serde", serde(with = "symbol::serde_chars"))]
+    pub addition: BTreeSet<char>,
+    pub filter: WordFilter,
+    pub context: Vec<String>,
+    pub keyboard: Option<KeyboardSetting>,
+    pub unicode: BTreeSet<UnicodeClass>,
+    pub unit: LengthUnit,
+}
+
+pub const LOWER_CLASS: &str = "lower";
+pub const UPPER_CLASS: &str = "upper";
+pub const NUMERIC_CLASS: &str = "numeric";
+pub const MARK_CLASS: &str = "mark";
+pub const ADDITION_CLASS: &str = "addition";
+
+/// class of character in password, which is used for coloring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CharClass {
+    Lower,
+    Upper,
+    Numeric,
+    Mark,
+    Addition,
+}
+
+impl CharClass {
+    pub const ALL: [CharClass; 5] = [
+        CharClass::Lower,
+        CharClass::Upper,
+        CharClass::Numeric,
+        CharClass::Mark,
+        CharClass::Addition,
+    ];
+
+    /// return name of this class, such as "lower", which is also CSS class of web
+    pub fn name(&self) -> &'static str {
+        match self {
+            CharClass::Lower => LOWER_CLASS,
+            CharClass::Upper => UPPER_CLASS,
+            CharClass::Numeric => NUMERIC_CLASS,
+            CharClass::Mark => MARK_CLASS,
+            CharClass::Addition => ADDITION_CLASS,
+        }
+    }
+}
+
+/// reason why existing password does not satisfy settings of generator
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckFailure {
+    /// password is shorter than this length, in the unit of generator
+    TooShort(usize),
+    /// password has no character of this class, such as "numeric"
+    MissingClass(&'static str),
+    /// password has this character, which generator never uses
+    UnavailableChar(char),
+    /// password contains this word of word filter
+    FilteredWord(String),
+    /// password contains this token of context
+    ContainsContext(String),
+}
+
+impl fmt::Display for CheckFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort(len) => write!(f, "password should be at least {} long", len),
+            Self::MissingClass(class) => write!(f, "password has no {} character", class),
+            Self::UnavailableChar(c) => write!(f, "password has unavailable character {:?}", c),
+            Self::FilteredWord(word) => write!(f, "password contains filtered word {:?}", word),
+            Self::ContainsContext(token) => write!(f, "password contains context {:?}", token),
+        }
+    }
+}
+
+impl Default for PasswordGenerator {
+    fn default() -> Self {
+        Self {
+            len: PASSWORD_DEFAULT_LENGTH,
+            use_lower: true,
+            use_upper: true,
+            use_numeric: true,
+            mark: symbol::MarkSet::default(),
+            addition: BTreeSet::new(),
+            filter: WordFilter::new(),
+            context: Vec::new(),
+            keyboard: None,
+            unicode: BTreeSet::new(),
+            unit: LengthUnit::Chars,
+        }
+    }
+}
+
+impl PasswordGenerator {
+    /// get new generator
+    pub fn new(
+        len: usize,
+        use_lower: bool,
+        use_upper: bool,
+        use_numeric: bool,
+        mark: MarkSet,
+        addition: BTreeSet<char>,
+    ) -> Self {
+        Self {
+            len,
+            use_lower,
+            use_upper,
+            use_numeric,
+            mark,
+            addition,
+            filter: WordFilter::new(),
+            context: Vec::new(),
+            keyboard: None,
+            unicode: BTreeSet::new(),
+            unit: LengthUnit::Chars,
+        }
+    }
+
+    /// generate password. (this method redraw until use_* is satisfied)
+    #[cfg(feature = "std")]
+    pub fn generate_password(&self) -> anyhow::Result<Password> {
+        self.generate_password_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate password with given CSPRNG, such as hardware RNG of firmware or enclave
+    pub fn generate_password_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        let (password, _redraw) = self.generate_password_with_redraw_with_rng(csp_rng)?;
+        Ok(password)
+    }
+
+    /// generate password, and return it with the number of redraws caused by word filter or context
+    #[cfg(feature = "std")]
+    pub fn generate_password_with_redraw(&self) -> anyhow::Result<(Password, usize)> {
+        self.generate_password_with_redraw_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate password with given CSPRNG, and return it with the number of redraws
+    pub fn generate_password_with_redraw_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<(Password, usize)> {
+        self.can_generate()?;
+        let chars = self.get_chars();
+        let sampler = self.get_switch_sampler()?;
+        let tokens = self.get_context_tokens();
+        let (mut redraw, mut invalid) = (0, 0);
+        let password = loop {
+            let pw = match (&sampler, self.unit) {
+                (Some((sampler, max_switches)), _) => {
+                    sampler.sample_with_rng(*max_switches, csp_rng)?
+                }
+                (None, LengthUnit::Bytes) => {
+                    Password::generate_bytes_with_rng(self.len, &chars, csp_rng)?
+                }
+                (None, _) => Password::generate_with_rng(self.len, &chars, csp_rng)?,
+            };
+            let short = self.unit != LengthUnit::Bytes && self.unit.measure(&pw) != self.len;
+            if !self.validate(&pw) || short {
+                invalid += 1;
+                if invalid >= PASSWORD_MAX_INVALID_DRAW {
+                    Err(GeneratorError::TooManyRedraw(invalid))?
+                }
+            } else if self.filter.is_match(&pw) || Self::contains_any_token(&pw, &tokens) {
+                redraw += 1;
+                if redraw >= PASSWORD_MAX_REDRAW {
+                    Err(GeneratorError::TooManyRedraw(redraw))?
+                }
+            } else {
+                break pw;
+            }
+        };
+        Ok((password, redraw))
+    }
+
+    #[inline]
+    /// check generator state, which can generate password
+    pub fn can_generate(&self) -> anyhow::Result<()> {
+        if self.len == 0 {
+            Err(GeneratorError::EmptyLength)?
+        } else if self.len < PASSWORD_MIN_LENGTH {
+            Err(GeneratorError::TooShortLength(self.len))?
+        } else if self.len > PASSWORD_MAX_LENGTH {
+            Err(GeneratorError::TooLongLength(self.len))?
+        } else if self.get_chars().is_empty() {
+            Err(GeneratorError::EmptySymbol)?
+        } else if let Some(&c) = self.addition.iter().find(|&&c| !symbol::is_valid_char(c)) {
+            Err(GeneratorError::InvalidChar(c))?
+        } else if self.keyboard.is_some() && self.unit == LengthUnit::Bytes {
+            Err(GeneratorError::KeyboardWithBytes)?
+        } else if self.get_required_length() > self.len {
+            Err(GeneratorError::TooManyClasses(self.get_required_length(), self.len))?
+        } else {
+            self.get_switch_sampler()?;
+            Ok(())
+        }
+    }
+
+    /// entropy bits of password, as uniform choice from available chars or keyboard sampler.
+    /// requirement of each class and redraws decrease it slightly, which is ignored.
+    /// with bytes unit, the number of chars is estimated by the longest UTF-8 char
+    pub fn entropy(&self) -> anyhow::Result<f64> {
+        self.can_generate()?;
+        if let Some((sampler, max_switches)) = self.get_switch_sampler()? {
+            return Ok(sampler.entropy(max_switches));
+        }
+        let chars = self.get_chars();
+        let len = match self.unit {
+            LengthUnit::Bytes => self.len / chars.iter().map(|c| c.len_utf8()).max().unwrap_or(1),
+            _ => self.len,
+        };
+        Ok(len as f64 * libm::log2(chars.len() as f64))
+    }
+
+    /// get sampler and max layer switches of password, if keyboard is set
+    pub fn get_switch_sampler(&self) -> anyhow::Result<Option<(SwitchSampler, usize)>> {
+        let keyboard = match &self.keyboard {
+            Some(keyboard) => keyboard,
+            None => return Ok(None),
+        };
+        let sampler = SwitchSampler::new(keyboard.layout, self.len, &self.get_chars());
+        let required = self.get_required_switches(keyboard.layout);
+        let max_switches = match keyboard.max_switches {
+            Some(max_switches) if max_switches < required => {
+                Err(GeneratorError::TooFewSwitches(required, max_switches))?
+            }
+            Some(max_switches) => max_switches,
+            None => sampler.min_switches(keyboard.min_entropy).unwrap_or(self.len).max(required),
+        };
+        let entropy = sampler.entropy(max_switches);
+        if entropy < keyboard.min_entropy {
+            Err(GeneratorError::LowEntropy(entropy, keyboard.min_entropy))?
+        }
+        Ok(Some((sampler, max_switches)))
+    }
+
+    /// get min layer switches to type at least one character of each used class
+    pub fn get_required_switches(&self, layout: KeyboardLayout) -> usize {
+        let mut classes: Vec<Vec<char>> = Vec::new();
+        if self.use_lower {
+            classes.push(symbol::LOWER.chars().collect());
+        }
+        if self.use_upper {
+            classes.push(symbol::UPPER.chars().collect());
+        }
+        if self.use_numeric {
+            classes.push(symbol::NUMERIC.chars().collect());
+        }
+        if !self.mark.is_empty() {
+            classes.push(self.mark.iter().copied().collect());
+        }
+        if !self.addition.is_empty() {
+            classes.push(self.addition.iter().copied().collect());
+        }
+        for class in self.unicode.iter() {
+            classes.push(class.chars().collect());
+        }
+        // bit set of layers, in which each class can be typed. untypeable is an extra layer
+        let masks: Vec<usize> = classes
+            .iter()
+            .map(|class| {
+                class
+                    .iter()
+                    .fold(0, |mask, &c| mask | 1 << layout.layer(c).unwrap_or(KEYBOARD_LAYER_COUNT))
+            })
+            .collect();
+        // base layer can be used without switch, and other layers need a switch for each
+        (0..1 << (KEYBOARD_LAYER_COUNT + 1))
+            .filter(|layers| layers & 1 == 1)
+            .filter(|layers| masks.iter().all(|mask| mask & layers != 0))
+            .map(|layers: usize| layers.count_ones() as usize - 1)
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// get selected chars, which cannot be typed with the keyboard layout
+    pub fn get_untypeable_chars(&self) -> Vec<char> {
+        match &self.keyboard {
+            Some(keyboard) => {
+                let mut chars = keyboard.layout.untypeable(&self.get_chars());
+                chars.sort_unstable();
+                chars
+            }
+            None => Vec::new(),
+        }
+    }
+
+    #[inline]
+    /// get password available chars, based on generator state
+    pub fn get_chars(&self) -> Vec<char> {
+        let mut chars = BTreeSet::new();
+        if self.use_lower {
+            chars.extend(symbol::LOWER_SET.iter());
+        }
+        if self.use_upper {
+            chars.extend(symbol::UPPER_SET.iter());
+        }
+        if self.use_numeric {
+            chars.extend(symbol::NUMERIC_SET.iter());
+        }
+        if !self.mark.is_empty() {
+            chars.extend(self.mark.iter());
+        }
+        if !self.addition.is_empty() {
+            chars.extend(self.addition.iter());
+        }
+        for class in self.unicode.iter() {
+            chars.extend(class.chars());
+        }
+        chars.into_iter().collect()
+    }
+
+    /// get min length to contain one character of each selected class, which is counted in
+    /// UTF-8 bytes of the shortest character of each class if the unit is bytes
+    pub fn get_required_length(&self) -> usize {
+        let mut classes: Vec<Vec<char>> =
+            self.unicode.iter().map(|class| class.chars().collect()).collect();
+        if self.use_lower {
+            classes.push(symbol::LOWER_SET.to_vec());
+        }
+        if self.use_upper {
+            classes.push(symbol::UPPER_SET.to_vec());
+        }
+        if self.use_numeric {
+            classes.push(symbol::NUMERIC_SET.to_vec());
+        }
+        if !self.mark.is_empty() {
+            classes.push(self.mark.iter().copied().collect());
+        }
+        if !self.addition.is_empty() {
+            classes.push(self.addition.iter().copied().collect());
+        }
+        match self.unit {
+            LengthUnit::Bytes => classes
+                .iter()
+                .map(|chars| chars.iter().map(|c| c.len_utf8()).min().unwrap_or(0))
+                .sum(),
+            _ => classes.len(),
+        }
+    }
+
+    /// get lower case tokens of context, split by non alphanumeric character such as `.` or `@`
+    pub fn get_context_tokens(&self) -> Vec<String> {
+        self.context
+            .iter()
+            .flat_map(|context| context.split(|c: char| !c.is_alphanumeric()))
+            .filter(|token| token.chars().count() >= CONTEXT_MIN_TOKEN_LENGTH)
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// return true if password contains any token of context, case-insensitively
+    pub fn contains_context(&self, password: &Password) -> bool {
+        Self::contains_any_token(password, &self.get_context_tokens())
+    }
+
+    fn contains_any_token(password: &Password, tokens: &[String]) -> bool {
+        if tokens.is_empty() {
+            return false;
+        }
+        let lower = password.to_string().to_lowercase();
+        tokens.iter().any(|token| lower.contains(token.as_str()))
+    }
+
+    /// return class of character in password. addition takes precedence over other classes,
+    /// and characters of no class such as unicode class are displayed as addition
+    pub fn classify(&self, c: &char) -> CharClass {
+        if !self.addition.is_empty() && self.addition.contains(c) {
+            CharClass::Addition
+        } else if self.use_lower && symbol::LOWER_SET.contains(c) {
+            CharClass::Lower
+        } else if self.use_upper && symbol::UPPER_SET.contains(c) {
+            CharClass::Upper
+        } else if self.use_numeric && symbol::NUMERIC_SET.contains(c) {
+            CharClass::Numeric
+        } else if !self.mark.is_empty() && self.mark.contains(c) {
+            CharClass::Mark
+        } else {
+            CharClass::Addition
+        }
+    }
+
+    /// check existing password against settings, and return all failures, empty if it passes.
+    /// unlike `validate`, length is min length, and unused classes are not failures
+    pub fn check(&self, password: &Password) -> Vec<CheckFailure> {
+        let mut failures = Vec::new();
+        if self.unit.measure(password) < self.len {
+            failures.push(CheckFailure::TooShort(self.len));
+        }
+        let mut required: Vec<(&'static str, Vec<char>)> = Vec::new();
+        if self.use_lower {
+            required.push((CharClass::Lower.name(), symbol::LOWER_SET.to_vec()));
+        }
+        if self.use_upper {
+            required.push((CharClass::Upper.name(), symbol::UPPER_SET.to_vec()));
+        }
+        if self.use_numeric {
+            required.push((CharClass::Numeric.name(), symbol::NUMERIC_SET.to_vec()));
+        }
+        if !self.mark.is_empty() {
+            required.push((CharClass::Mark.name(), self.mark.iter().copied().collect()));
+        }
+        if !self.addition.is_empty() {
+            required.push((CharClass::Addition.name(), self.addition.iter().copied().collect()));
+        }
+        for class in self.unicode.iter() {
+            required.push((class.name(), class.chars().collect()));
+        }
+        for (name, chars) in required {
+            if !password.iter().any(|c| chars.contains(c)) {
+                failures.push(CheckFailure::MissingClass(name));
+            }
+        }
+        let chars = self.get_chars();
+        let mut unavailable: Vec<char> =
+            password.iter().filter(|c| !chars.contains(c)).copied().collect();
+        unavailable.sort_unstable();
+        unavailable.dedup();
+        failures.extend(unavailable.into_iter().map(CheckFailure::UnavailableChar));
+        if let Some(word) = self.filter.find(password) {
+            failures.push(CheckFailure::FilteredWord(word.into()));
+        }
+        let lower = password.to_string().to_lowercase();
+        for token in self.get_context_tokens() {
+            if lower.contains(token.as_str()) {
+                failures.push(CheckFailure::ContainsContext(token));
+            }
+        }
+        failures
+    }
+
+    /// validate password, such as is numeric used when use_numeric is true
+    pub fn validate(&self, password: &Password) -> bool {
+        let (mut used_lower, mut used_upper, mut used_numeric, mut used_mark, mut used_addition) =
+            (false, false, false, false, false);
+        for c in password.iter() {
+            used_lower |= symbol::LOWER_SET.contains(c);
+            used_upper |= symbol::UPPER_SET.contains(c);
+            used_numeric |= symbol::NUMERIC_SET.contains(c);
+            used_mark |= self.mark.contains(c);
+            used_addition |= self.addition.contains(c);
+        }
+        !(self.use_lower && !used_lower)            // if use_lower=false, addition can include lower alphabet.
+            && !(self.use_upper && !used_upper)     // so, return true only if "use_lower=true and used_lower=false"
+            && !(self.use_numeric && !used_numeric) // the code `!(self.use_lower && !used_lower)` can work so.
+            && !self.mark.is_empty() == used_mark
+            && !self.addition.is_empty() == used_addition
+            && self.unicode.iter().all(|class| password.iter().any(|c| class.contains(c)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::keyboard::KEYBOARD_DEFAULT_MIN_ENTROPY;
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn generator_test() {
+        // password example: 7UPCItcE^#NMDKaXQHo4
+        let password = PasswordGenerator::default().generate_password().unwrap();
+        let (mut used_lower, mut used_upper, mut used_numeric, mut used_mark) =
+            (false, false, false, false);
+        for c in password.iter() {
+            used_lower |= symbol::LOWER_SET.contains(c);
+            used_upper |= symbol::UPPER_SET.contains(c);
+            used_numeric |= symbol::NUMERIC_SET.contains(c);
+            used_mark |= symbol::MarkSet::DEFAULT_MARK_SET.contains(c);
+            assert!(
+                symbol::LOWER_SET.contains(c)
+                    || symbol::UPPER_SET.contains(c)
+                    || symbol::NUMERIC_SET.contains(c)
+                    || symbol::MarkSet::DEFAULT_MARK_SET.contains(c)
+            )
+        }
+        assert!(used_lower && used_upper && used_numeric && used_mark);
+    }
+
+    #[test]
+    fn generate_with_rng_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.keyboard = Some(KeyboardSetting::new(KeyboardLayout::Ios));
+        let a = generator.generate_password_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        let b = generator.generate_password_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        assert!(generator.validate(&a));
+        assert_eq!(a.to_string(), b.to_string());
+    }
+
+    #[test]
+    fn generate_uniqueness_test() {
+        let generator = PasswordGenerator::default();
+        let mut set = BTreeSet::new();
+        for _ in 0..500 {
+            set.insert(format!("{:?}", generator.generate_password().unwrap()));
+        }
+        assert_eq!(set.len(), 500);
+    }
+
+    #[test]
+    fn generate_error_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.len = 0;
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "password should not be empty"
+        );
+        generator.len = 7;
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "password should be longer than 8, but given is 7"
+        );
+        generator.len = 129;
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "password max length is 128, for convenience such as GUI, but given is 129",
+        );
+        generator.len = 8;
+        generator.use_lower = false;
+        generator.use_upper = false;
+        generator.use_numeric = false;
+        generator.mark.clear();
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "because no available symbol, cannot generate a password"
+        );
+    }
+
+    #[test]
+    fn generator_setting_no_numeric_but_added_numeric_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.use_numeric = false;
+        generator.addition = (0..=9).map(|i| std::char::from_digit(i, 10).unwrap()).collect();
+        let generated_password = generator.generate_password().unwrap();
+        assert!(generator.validate(&generated_password));
+        // password example: pEamK5KhY0Ig6bB4lWZF
+        // ^ generator.use_numeric=false, but addition include numeric, so password include numeric
+    }
+
+    #[test]
+    fn generator_setting_no_mark_but_added_mark_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.mark.clear();
+        generator.addition = vec!['@', '#'].into_iter().collect();
+        let generated_password = generator.generate_password().unwrap();
+        assert!(generator.validate(&generated_password));
+        // password example: YbSlFZyh1OOzP11jG#n@
+        // ^ generator's mark is empty, but addition include mark, so password include mark
+    }
+
+    #[test]
+    fn generator_setting_added_lower_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.addition = vec!['a'].into_iter().collect();
+        let generated_password = generator.generate_password().unwrap();
+        assert!(generator.validate(&generated_password));
+        assert!(generated_password.iter().any(|&c| c == 'a'));
+        // password example: 8KF#udC1T9bFadIEtPp4
+        // ^ addition contains only 'a', so output password must contains 'a'
+    }
+
+    #[test]
+    fn generator_setting_added_mark_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.addition = vec!['@', '#'].into_iter().collect();
+        let generated_password = generator.generate_password().unwrap();
+        assert!(generator.validate(&generated_password));
+        // password example: k#31V05K37p6YVbWJGFg
+    }
+
+    #[test]
+    fn generator_setting_only_a_test() {
+        let generator = PasswordGenerator::new(
+            20,
+            false,
+            false,
+            false,
+            MarkSet::new(),
+            vec!['a'].into_iter().collect(),
+        );
+        let generated_password = generator.generate_password().unwrap();
+        assert_eq!(format!("{:?}", generated_password), "aaaaaaaaaaaaaaaaaaaa");
+    }
+
+    #[test]
+    fn generator_filter_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.use_upper = false;
+        generator.use_numeric = false;
+        generator.mark.clear();
+        generator.addition = vec!['a', 'b'].into_iter().collect();
+        generator.len = 8;
+        generator.filter.insert("aaa");
+        generator.filter.insert("bbb");
+        for _ in 0..100 {
+            let password = generator.generate_password().unwrap();
+            assert!(!password.to_string().contains("aaa"));
+            assert!(!password.to_string().contains("bbb"));
+        }
+    }
+
+    #[test]
+    fn generator_too_many_redraw_test() {
+        let mut generator = PasswordGenerator::new(
+            20,
+            false,
+            false,
+            false,
+            MarkSet::new(),
+            vec!['a'].into_iter().collect(),
+        );
+        generator.filter.insert("aaa");
+        assert_eq!(
+            generator.generate_password_with_redraw().unwrap_err().to_string(),
+            "password was redrawn 1000 times, but all of them lacked some class or contained filtered word or context"
+        );
+    }
+
+    #[test]
+    fn generator_keyboard_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.keyboard = Some(KeyboardSetting {
+            layout: KeyboardLayout::Ios,
+            max_switches: Some(4),
+            min_entropy: 0.,
+        });
+        for _ in 0..100 {
+            let password = generator.generate_password().unwrap();
+            assert!(generator.validate(&password));
+            assert!(KeyboardLayout::Ios.switches(password.iter()) <= 4);
+        }
+    }
+
+    #[test]
+    fn generator_minimize_switches_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.keyboard = Some(KeyboardSetting::new(KeyboardLayout::Us));
+        let (sampler, max_switches) = generator.get_switch_sampler().unwrap().unwrap();
+        assert_eq!(max_switches, 1); // lower and numeric are in base layer, upper and mark in shift
+        assert!(sampler.entropy(max_switches) >= KEYBOARD_DEFAULT_MIN_ENTROPY);
+        let password = generator.generate_password().unwrap();
+        assert!(KeyboardLayout::Us.switches(password.iter()) <= 1);
+
+        generator.len = 128;
+        generator.keyboard.as_mut().unwrap().min_entropy = 700.;
+        let (sampler, max_switches) = generator.get_switch_sampler().unwrap().unwrap();
+        assert!(sampler.entropy(max_switches) >= 700.);
+        assert!(sampler.entropy(max_switches - 1) < 700.);
+
+        generator.keyboard.as_mut().unwrap().min_entropy = 1000.;
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "password entropy is 776.5 bits, but it should be at least 1000.0 bits"
+        );
+    }
+
+    #[test]
+    fn generator_required_switches_test() {
+        let mut generator = PasswordGenerator::default();
+        assert_eq!(generator.get_required_switches(KeyboardLayout::Us), 1);
+        assert_eq!(generator.get_required_switches(KeyboardLayout::Ios), 2); // "!@&" in number layer
+        generator.mark = MarkSet::new();
+        generator.mark.insert('#');
+        assert_eq!(generator.get_required_switches(KeyboardLayout::Ios), 3); // "#" in symbol layer
+        generator.keyboard = Some(KeyboardSetting {
+            layout: KeyboardLayout::Ios,
+            max_switches: Some(2),
+            min_entropy: 0.,
+        });
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "password needs at least 3 layer switches to use all selected characters, but max is 2"
+        );
+    }
+
+    #[test]
+    fn generator_untypeable_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.addition = vec!['€', 'a'].into_iter().collect();
+        assert!(generator.get_untypeable_chars().is_empty());
+        generator.keyboard = Some(KeyboardSetting::new(KeyboardLayout::Jis));
+        assert_eq!(generator.get_untypeable_chars(), vec!['€']);
+    }
+
+    #[test]
+    fn generator_unicode_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.unicode = vec![UnicodeClass::Greek, UnicodeClass::Kana].into_iter().collect();
+        for _ in 0..100 {
+            let password = generator.generate_password().unwrap();
+            assert!(password.iter().any(|c| UnicodeClass::Greek.contains(c)));
+            assert!(password.iter().any(|c| UnicodeClass::Kana.contains(c)));
+        }
+    }
+
+    #[test]
+    fn generator_invalid_addition_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.addition = vec!['\u{0301}'].into_iter().collect();
+        assert_eq!(
+            generator.generate_password().unwrap_err().to_string(),
+            "'\\u{301}' is control, format, unassigned or combining character, so cannot be used"
+        );
+        generator.addition = vec!['\u{7}'].into_iter().collect();
+        assert!(generator.generate_password().is_err());
+    }
+
+    #[test]
+    fn generator_length_unit_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.unicode = vec![UnicodeClass::Emoji].into_iter().collect();
+        generator.unit = LengthUnit::Bytes;
+        generator.len = 16;
+        for _ in 0..100 {
+            let password = generator.generate_password().unwrap();
+            assert_eq!(LengthUnit::Bytes.measure(&password), 16);
+            assert!(generator.validate(&password));
+        }
+        generator.unit = LengthUnit::Graphemes;
+        let password = generator.generate_password().unwrap();
+        assert_eq!(LengthUnit::Graphemes.measure(&password), 16);
+        generator.keyboard = Some(KeyboardSetting::new(KeyboardLayout::Us));
+        generator.unit = LengthUnit::Bytes;
+        assert!(generator.generate_password().is_err());
+    }
+
+    #[test]
+    fn generator_too_many_classes_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.unicode = UnicodeClass::ALL.iter().copied().collect();
+        generator.len = 8;
+        let error = generator.generate_password().unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(GeneratorError::TooManyClasses(9, 8))));
+        assert_eq!(crate::error_kind(&error), "TooManyClasses");
+        generator.len = 20;
+        assert!(generator.validate(&generator.generate_password().unwrap()));
+
+        // emoji needs 4 bytes and kana needs 3 bytes, in addition to 4 ASCII classes
+        generator.unicode = vec![UnicodeClass::Emoji, UnicodeClass::Kana].into_iter().collect();
+        generator.unit = LengthUnit::Bytes;
+        assert_eq!(generator.get_required_length(), 11);
+        generator.len = 10;
+        assert!(generator.can_generate().is_err());
+        generator.len = 11;
+        assert!(generator.can_generate().is_ok());
+    }
+
+    #[test]
+    fn generator_entropy_test() {
+        let mut generator = PasswordGenerator::default();
+        // 26 lower, 26 upper, 10 numeric and 5 marks
+        assert!((generator.entropy().unwrap() - 20. * 67f64.log2()).abs() < 1e-9);
+        generator.unit = LengthUnit::Bytes;
+        generator.addition = vec!['π'].into_iter().collect();
+        assert!((generator.entropy().unwrap() - 10. * 68f64.log2()).abs() < 1e-9);
+        generator.unit = LengthUnit::Chars;
+        generator.addition.clear();
+        generator.keyboard = Some(KeyboardSetting::new(KeyboardLayout::Us));
+        let (sampler, max_switches) = generator.get_switch_sampler().unwrap().unwrap();
+        assert_eq!(generator.entropy().unwrap(), sampler.entropy(max_switches));
+        generator.len = 0;
+        assert!(generator.entropy().is_err());
+    }
+
+    #[test]
+    fn classify_test() {
+        let mut generator = PasswordGenerator::default();
+        let classes: Vec<_> = "aZ5^~".chars().map(|c| generator.classify(&c)).collect();
+        assert_eq!(
+            classes,
+            vec![
+                CharClass::Lower,
+                CharClass::Upper,
+                CharClass::Numeric,
+                CharClass::Mark,
+                CharClass::Addition
+            ]
+        );
+        generator.addition = vec!['a', '~'].into_iter().collect();
+        generator.use_upper = false;
+        assert_eq!(generator.classify(&'a'), CharClass::Addition);
+        assert_eq!(generator.classify(&'Z'), CharClass::Addition);
+        assert_eq!(generator.classify(&'b').name(), "lower");
+        assert_eq!(
+            CharClass::ALL.iter().map(CharClass::name).collect::<Vec<_>>().join(" "),
+            "lower upper numeric mark addition"
+        );
+    }
+
+    #[test]
+    fn check_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.len = 8;
+        let password = Password::from_chars(&"Pa55word!@".chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(generator.check(&password), Vec::new());
+        let password = Password::from_chars(&"password~".chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(
+            generator.check(&password),
+            vec![
+                CheckFailure::MissingClass("upper"),
+                CheckFailure::MissingClass("numeric"),
+                CheckFailure::MissingClass("mark"),
+                CheckFailure::UnavailableChar('~'),
+            ]
+        );
+        generator.len = 20;
+        generator.filter.insert("word");
+        generator.context = vec!["alice@example.com".to_string()];
+        let password = Password::from_chars(&"Alice5Word!".chars().collect::<Vec<_>>()).unwrap();
+        assert_eq!(
+            generator.check(&password),
+            vec![
+                CheckFailure::TooShort(20),
+                CheckFailure::FilteredWord("word".to_string()),
+                CheckFailure::ContainsContext("alice".to_string()),
+            ]
+        );
+        assert_eq!(CheckFailure::TooShort(20).to_string(), "password should be at least 20 long");
+    }
+
+    #[test]
+    fn context_tokens_test() {
+        let mut generator = PasswordGenerator::default();
+        generator.context = vec!["John.Smith@example.com".to_string(), "ab-cd".to_string()];
+        assert_eq!(generator.get_context_tokens(), vec!["john", "smith", "example", "com"]);
+    }
+
+    #[test]
+    fn generator_context_test() {
+        let mut generator = PasswordGenerator::new(
+            8,
+            false,
+            false,
+            false,
+            MarkSet::new(),
+            vec!['a', 'B'].into_iter().collect(),
+        );
+        generator.context = vec!["bAb".to_string()];
+        for _ in 0..100 {
+            let password = generator.generate_password().unwrap();
+            assert!(!password.to_string().to_lowercase().contains("bab"));
+            assert!(!generator.contains_context(&password));
+        }
+        let password = Password::from_chars(&['a', 'B', 'a', 'b']).unwrap();
+        assert!(generator.contains_context(&password));
+    }
+}
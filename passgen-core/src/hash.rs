@@ -0,0 +1,284 @@
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use core::str::FromStr;
+use pbkdf2::Pbkdf2;
+use rand::{CryptoRng, Rng, RngCore};
+use sha_crypt::{sha512_crypt_b64, Sha512Params};
+
+use super::{otp::constant_time_eq, HashError, ParseError};
+
+/// bytes of random salt, 128 bits as recommended by NIST SP 800-132
+pub const HASH_SALT_BYTES: usize = 16;
+
+/// characters of salt of SHA-512-crypt, which is max length of glibc
+pub const SHA512_CRYPT_SALT_LENGTH: usize = 16;
+
+/// rounds of SHA-512-crypt, default of glibc which is omitted from hash as `openssl passwd -6`
+pub const SHA512_CRYPT_ROUNDS: usize = 5_000;
+
+/// alphabet of salt of crypt(3)
+pub const CRYPT_SALT_ALPHABET: &str =
+    "./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// cost of bcrypt, that is 2^12 rounds
+pub const BCRYPT_COST: u32 = 12;
+
+/// bcrypt ignores bytes after 72 bytes of password
+pub const BCRYPT_MAX_PASSWORD_BYTES: usize = 72;
+
+/// scheme of password hash, which is stored instead of password
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HashScheme {
+    /// `$6$` of crypt(3), used by /etc/shadow
+    Sha512Crypt,
+    /// `$2y$` of htpasswd, used by nginx and apache basic auth
+    Bcrypt,
+    /// PHC string of Argon2id with OWASP recommended parameters (m=19456,t=2,p=1)
+    #[default]
+    Argon2id,
+    /// PHC string of PBKDF2-HMAC-SHA256 with 600000 iterations as OWASP
+    Pbkdf2Sha256,
+}
+
+impl FromStr for HashScheme {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|scheme| scheme.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownHashScheme(s.to_string()))
+    }
+}
+
+impl HashScheme {
+    pub const ALL: [HashScheme; 4] = [
+        HashScheme::Sha512Crypt,
+        HashScheme::Bcrypt,
+        HashScheme::Argon2id,
+        HashScheme::Pbkdf2Sha256,
+    ];
+
+    /// return name of this scheme, such as "sha512-crypt"
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashScheme::Sha512Crypt => "sha512-crypt",
+            HashScheme::Bcrypt => "bcrypt",
+            HashScheme::Argon2id => "argon2id",
+            HashScheme::Pbkdf2Sha256 => "pbkdf2-sha256",
+        }
+    }
+
+    /// return scheme of given hash by its prefix, such as `$6$`
+    pub fn detect(hash: &str) -> Option<Self> {
+        if hash.starts_with("$6$") {
+            Some(HashScheme::Sha512Crypt)
+        } else if ["$2a$", "$2b$", "$2y$"].iter().any(|prefix| hash.starts_with(prefix)) {
+            Some(HashScheme::Bcrypt)
+        } else if hash.starts_with("$argon2id$") {
+            Some(HashScheme::Argon2id)
+        } else if hash.starts_with("$pbkdf2-sha256$") {
+            Some(HashScheme::Pbkdf2Sha256)
+        } else {
+            None
+        }
+    }
+
+    /// hash password with random salt from CSPRNG
+    pub fn hash(&self, password: &str) -> anyhow::Result<String> {
+        self.hash_with_rng(password, &mut rand::thread_rng())
+    }
+
+    /// hash password with random salt from given CSPRNG
+    pub fn hash_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        password: &str,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<String> {
+        let mut salt = [0u8; HASH_SALT_BYTES];
+        csp_rng.fill_bytes(&mut salt);
+        match self {
+            HashScheme::Sha512Crypt => {
+                let alphabet = CRYPT_SALT_ALPHABET.as_bytes();
+                let salt: String = (0..SHA512_CRYPT_SALT_LENGTH)
+                    .map(|_| alphabet[csp_rng.gen_range(0, alphabet.len())] as char)
+                    .collect();
+                sha512_crypt(password, SHA512_CRYPT_ROUNDS, &salt)
+            }
+            HashScheme::Bcrypt => {
+                if password.len() > BCRYPT_MAX_PASSWORD_BYTES {
+                    Err(HashError::TooLongForBcrypt(password.len()))?
+                }
+                let parts = bcrypt::hash_with_salt(password, BCRYPT_COST, salt)?;
+                Ok(parts.format_for_version(bcrypt::Version::TwoY))
+            }
+            HashScheme::Argon2id => {
+                let salt = SaltString::encode_b64(&salt)?;
+                Ok(Argon2::default().hash_password(password.as_bytes(), &salt)?.to_string())
+            }
+            HashScheme::Pbkdf2Sha256 => {
+                let salt = SaltString::encode_b64(&salt)?;
+                Ok(Pbkdf2.hash_password(password.as_bytes(), &salt)?.to_string())
+            }
+        }
+    }
+}
+
+/// return true if password matches hash of any scheme, with parameters and salt in the hash
+pub fn verify_hash(password: &str, hash: &str) -> anyhow::Result<bool> {
+    match HashScheme::detect(hash) {
+        Some(HashScheme::Sha512Crypt) => {
+            // only hash field is compared, because `rounds=5000` may be written or omitted
+            let (rounds, salt, hash) = parse_sha512_crypt(hash)?;
+            let expected = sha512_crypt_hash(password, rounds, salt)?;
+            Ok(constant_time_eq(expected.as_bytes(), hash.as_bytes()))
+        }
+        Some(HashScheme::Bcrypt) => {
+            if password.len() > BCRYPT_MAX_PASSWORD_BYTES {
+                return Ok(false);
+            }
+            Ok(bcrypt::verify(password, hash)?)
+        }
+        Some(HashScheme::Argon2id) | Some(HashScheme::Pbkdf2Sha256) => {
+            let parsed = PasswordHash::new(hash)?;
+            let verifiers: [&dyn PasswordVerifier; 2] = [&Argon2::default(), &Pbkdf2];
+            Ok(parsed.verify_password(&verifiers, password).is_ok())
+        }
+        None => Err(HashError::UnknownFormat)?,
+    }
+}
+
+/// return `$6$[rounds=<rounds>$]<salt>$<hash>` of crypt(3)
+fn sha512_crypt(password: &str, rounds: usize, salt: &str) -> anyhow::Result<String> {
+    let hash = sha512_crypt_hash(password, rounds, salt)?;
+    match rounds {
+        SHA512_CRYPT_ROUNDS => Ok(format!("$6${}${}", salt, hash)),
+        _ => Ok(format!("$6$rounds={}${}${}", rounds, salt, hash)),
+    }
+}
+
+/// return hash field of SHA-512-crypt, which is encoded in crypt(3) base64
+fn sha512_crypt_hash(password: &str, rounds: usize, salt: &str) -> anyhow::Result<String> {
+    let params = Sha512Params::new(rounds).map_err(|_| HashError::InvalidRounds(rounds))?;
+    Ok(sha512_crypt_b64(password.as_bytes(), salt.as_bytes(), &params)
+        .map_err(|_| HashError::InvalidRounds(rounds))?)
+}
+
+/// return rounds, salt and hash field of SHA-512-crypt hash
+fn parse_sha512_crypt(hash: &str) -> anyhow::Result<(usize, &str, &str)> {
+    let rest = hash.strip_prefix("$6$").ok_or(HashError::UnknownFormat)?;
+    let (rounds, rest) = match rest.strip_prefix("rounds=") {
+        Some(rest) => {
+            let (rounds, rest) = rest.split_once('$').ok_or(HashError::UnknownFormat)?;
+            (rounds.parse().map_err(|_| HashError::UnknownFormat)?, rest)
+        }
+        None => (SHA512_CRYPT_ROUNDS, rest),
+    };
+    let (salt, hash) = rest.split_once('$').ok_or(HashError::UnknownFormat)?;
+    if salt.len() > SHA512_CRYPT_SALT_LENGTH {
+        Err(HashError::UnknownFormat)?
+    }
+    Ok((rounds, salt, hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn sha512_crypt_test() {
+        // test vectors of glibc crypt(3) specification by Ulrich Drepper
+        let hash = sha512_crypt("Hello world!", 5000, "saltstring").unwrap();
+        assert_eq!(hash, "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1");
+        let hash = "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v.";
+        assert_eq!(sha512_crypt("Hello world!", 10000, "saltstringsaltst").unwrap(), hash);
+        assert!(verify_hash("Hello world!", hash).unwrap());
+        assert!(!verify_hash("Hello world?", hash).unwrap());
+        // explicit default rounds, whose salt is truncated to 16 characters
+        let hash = "$6$rounds=5000$toolongsaltstrin$lQ8jolhgVRVhY4b5pZKaysCLi0QBxGoNeKQzQ3glMhwllF7oGDZxUhx1yxdYcz/e1JSbq3y6JMxxl8audkUEm0";
+        assert!(verify_hash("This is just a test", hash).unwrap());
+        assert!(!verify_hash("This is just a tesT", hash).unwrap());
+        let hash = "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1";
+        assert!(verify_hash("Hello world!", hash).unwrap());
+    }
+
+    #[test]
+    fn bcrypt_test() {
+        // test vector of OpenBSD bcrypt, $2b$ and $2y$ are the same algorithm
+        let hash = "$2b$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+        assert!(verify_hash("U*U", hash).unwrap());
+        assert!(verify_hash("U*U", &hash.replace("$2b$", "$2y$")).unwrap());
+        assert!(!verify_hash("U*V", hash).unwrap());
+    }
+
+    #[test]
+    fn argon2id_test() {
+        // test vector of reference implementation of Argon2
+        let hash = "$argon2id$v=19$m=65536,t=2,p=1$c29tZXNhbHQ$CTFhFdXPJO1aFaMaO6Mm5c8y7cJHAph8ArZWb2GRPPc";
+        assert!(verify_hash("password", hash).unwrap());
+        assert!(!verify_hash("passwore", hash).unwrap());
+    }
+
+    #[test]
+    fn pbkdf2_sha256_test() {
+        // test vector of RFC 7914 section 11
+        let hash = "$pbkdf2-sha256$i=1,l=64$c2FsdA$VawEblbjCJ/sFpHCJUS2BflBhSFt3gRl5oudV8INrLxJypzM8Xm2RZkWZLOdd+8xfHG4RbHjC9UJESBB06GXgw";
+        assert!(verify_hash("passwd", hash).unwrap());
+        assert!(!verify_hash("passwe", hash).unwrap());
+    }
+
+    #[test]
+    fn hash_and_verify_test() {
+        let prefixes =
+            ["$6$", "$2y$12$", "$argon2id$v=19$m=19456,t=2,p=1$", "$pbkdf2-sha256$i=600000,l=32$"];
+        for (scheme, prefix) in HashScheme::ALL.iter().zip(prefixes.iter()) {
+            let hash = scheme.hash("correct horse battery staple").unwrap();
+            assert!(hash.starts_with(prefix));
+            assert_eq!(HashScheme::detect(&hash), Some(*scheme));
+            assert!(verify_hash("correct horse battery staple", &hash).unwrap());
+            assert!(!verify_hash("correct horse battery stapler", &hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn salt_test() {
+        // default rounds is omitted, and salt is 16 characters of crypt alphabet
+        let hash = HashScheme::Sha512Crypt.hash("password").unwrap();
+        let (rounds, salt, _hash) = parse_sha512_crypt(&hash).unwrap();
+        assert_eq!((rounds, salt.len()), (SHA512_CRYPT_ROUNDS, SHA512_CRYPT_SALT_LENGTH));
+        assert!(salt.chars().all(|c| CRYPT_SALT_ALPHABET.contains(c)));
+        // different salt for each hash
+        assert_ne!(hash, HashScheme::Sha512Crypt.hash("password").unwrap());
+        let a =
+            HashScheme::Bcrypt.hash_with_rng("password", &mut StdRng::seed_from_u64(0)).unwrap();
+        let b =
+            HashScheme::Bcrypt.hash_with_rng("password", &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_ne!(a[..29], b[..29]);
+        let c =
+            HashScheme::Bcrypt.hash_with_rng("password", &mut StdRng::seed_from_u64(0)).unwrap();
+        assert_eq!(a, c);
+    }
+
+    #[test]
+    fn invalid_test() {
+        let error = HashScheme::Bcrypt.hash(&"a".repeat(73)).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(HashError::TooLongForBcrypt(73))));
+        let error = verify_hash("password", "$1$md5crypt$hash").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(HashError::UnknownFormat)));
+        let error = verify_hash("password", "$6$rounds=10$salt$hash").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(HashError::InvalidRounds(10))));
+        assert!(verify_hash("password", "$pbkdf2-sha256$i=1$c2FsdA$!!!").is_err());
+    }
+
+    #[test]
+    fn parse_hash_scheme_test() {
+        for scheme in HashScheme::ALL.iter() {
+            assert_eq!(scheme.name().parse::<HashScheme>().unwrap(), *scheme);
+        }
+        assert!("md5-crypt".parse::<HashScheme>().is_err());
+    }
+}
@@ -0,0 +1,331 @@
+use alloc::{string::ToString, vec, vec::Vec};
+use core::str::FromStr;
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{
+    password::Password,
+    symbol::{LOWER, NUMERIC, UPPER},
+    GeneratorError, ParseError,
+};
+
+/// the number of layers, base, shift, and two more layers such as AltGr or symbol keyboard
+pub const KEYBOARD_LAYER_COUNT: usize = 4;
+
+/// default min entropy bits of password, when layer switches are minimized
+pub const KEYBOARD_DEFAULT_MIN_ENTROPY: f64 = 64.0;
+
+/// keyboard layout, which decide the layer of each character
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyboardLayout {
+    Us,
+    Jis,
+    Azerty,
+    Qwertz,
+    Ios,
+    Android,
+}
+
+impl FromStr for KeyboardLayout {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|layout| layout.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownLayout(s.to_string()))
+    }
+}
+
+impl KeyboardLayout {
+    pub const ALL: [KeyboardLayout; 6] = [
+        KeyboardLayout::Us,
+        KeyboardLayout::Jis,
+        KeyboardLayout::Azerty,
+        KeyboardLayout::Qwertz,
+        KeyboardLayout::Ios,
+        KeyboardLayout::Android,
+    ];
+
+    /// return name of this layout, such as "us"
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyboardLayout::Us => "us",
+            KeyboardLayout::Jis => "jis",
+            KeyboardLayout::Azerty => "azerty",
+            KeyboardLayout::Qwertz => "qwertz",
+            KeyboardLayout::Ios => "ios",
+            KeyboardLayout::Android => "android",
+        }
+    }
+
+    /// return characters of each layer except alphabet and numeric, which are common in layouts
+    fn marks(&self) -> [&'static str; KEYBOARD_LAYER_COUNT] {
+        match self {
+            KeyboardLayout::Us => ["`-=[]\\;',./", "~!@#$%^&*()_+{}|:\"<>?", "", ""],
+            KeyboardLayout::Jis => ["-^\\@[;:],./", "!\"#$%&'()=~|`{+*}<>?_", "", ""],
+            KeyboardLayout::Azerty => ["&\"'(-_)=^$*,;:!<", "+%?./>", "~#{[|`\\@]}", ""],
+            KeyboardLayout::Qwertz => ["+#-.,<^", "!\"$%&/()=?`*'_:;>", "{[]}\\~@|", ""],
+            KeyboardLayout::Ios => ["", "", "-/:;()$&@\".,?!'", "[]{}#%^*+=_\\|~<>"],
+            KeyboardLayout::Android => [",.", "", "@#$_&-+()/*\"':;!?", "~`|^={}\\%[]<>"],
+        }
+    }
+
+    /// return layer of the character, 0 is base layer, and `None` if it cannot be typed
+    pub fn layer(&self, c: char) -> Option<usize> {
+        let numeric_layer = match self {
+            KeyboardLayout::Azerty => 1,
+            KeyboardLayout::Ios | KeyboardLayout::Android => 2,
+            _ => 0,
+        };
+        if LOWER.contains(c) {
+            Some(0)
+        } else if UPPER.contains(c) {
+            Some(1)
+        } else if NUMERIC.contains(c) {
+            Some(numeric_layer)
+        } else {
+            self.marks().iter().position(|layer| layer.contains(c))
+        }
+    }
+
+    /// return characters which cannot be typed with this layout
+    pub fn untypeable<'a>(&self, chars: impl IntoIterator<Item = &'a char>) -> Vec<char> {
+        chars.into_iter().filter(|&&c| self.layer(c).is_none()).copied().collect()
+    }
+
+    /// count layer switches to type characters from base layer, untypeable character is counted
+    /// as an extra layer
+    pub fn switches<'a>(&self, chars: impl IntoIterator<Item = &'a char>) -> usize {
+        let mut current = 0;
+        let mut switches = 0;
+        for &c in chars {
+            let layer = self.layer(c).unwrap_or(KEYBOARD_LAYER_COUNT);
+            if layer != current {
+                switches += 1;
+                current = layer;
+            }
+        }
+        switches
+    }
+}
+
+/// keyboard setting of password generator
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KeyboardSetting {
+    pub layout: KeyboardLayout,
+    /// max layer switches, or `None` to minimize switches keeping min entropy
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_switches: Option<usize>,
+    /// min entropy bits of password
+    #[cfg_attr(feature = "serde", serde(default = "default_min_entropy"))]
+    pub min_entropy: f64,
+}
+
+#[cfg(feature = "serde")]
+fn default_min_entropy() -> f64 {
+    KEYBOARD_DEFAULT_MIN_ENTROPY
+}
+
+impl KeyboardSetting {
+    /// get new keyboard setting, which minimize layer switches
+    pub fn new(layout: KeyboardLayout) -> Self {
+        Self { layout, max_switches: None, min_entropy: KEYBOARD_DEFAULT_MIN_ENTROPY }
+    }
+}
+
+/// Sampler which draws password uniformly from the passwords whose layer switches are capped.
+///
+/// `table[i][l][r]` is log2 of the number of ways to type the rest `len - i` characters from
+/// layer `l` with at most `r` switches. Counts themselves overflow f64 with long password of
+/// large alphabet, so they are held in log space, where zero count is negative infinity.
+#[derive(Debug, Clone)]
+pub struct SwitchSampler {
+    len: usize,
+    layers: Vec<Vec<char>>,
+    table: Vec<f64>,
+}
+
+impl SwitchSampler {
+    /// build sampler of passwords of given length from given chars
+    pub fn new(layout: KeyboardLayout, len: usize, chars: &[char]) -> Self {
+        let mut layers = vec![Vec::new(); KEYBOARD_LAYER_COUNT + 1];
+        for &c in chars {
+            layers[layout.layer(c).unwrap_or(KEYBOARD_LAYER_COUNT)].push(c);
+        }
+        let table = vec![f64::NEG_INFINITY; (len + 1) * layers.len() * (len + 1)];
+        let mut sampler = Self { len, layers, table };
+        for i in (0..=len).rev() {
+            for l in 0..sampler.layers.len() {
+                for r in 0..=len {
+                    let log_count = if i == len {
+                        0.
+                    } else {
+                        let weights: Vec<_> = (0..sampler.layers.len())
+                            .map(|m| sampler.log_weight(i, l, r, m))
+                            .collect();
+                        log2_sum(&weights)
+                    };
+                    let index = sampler.index(i, l, r);
+                    sampler.table[index] = log_count;
+                }
+            }
+        }
+        sampler
+    }
+
+    fn index(&self, i: usize, l: usize, r: usize) -> usize {
+        (i * self.layers.len() + l) * (self.len + 1) + r
+    }
+
+    /// log2 of the number of ways to type character of layer `m` at `i`, and then the rest
+    fn log_weight(&self, i: usize, l: usize, r: usize, m: usize) -> f64 {
+        let rest = match (l == m, r) {
+            (true, r) => r,
+            (false, 0) => return f64::NEG_INFINITY,
+            (false, r) => r - 1,
+        };
+        match self.layers[m].len() {
+            0 => f64::NEG_INFINITY,
+            n => libm::log2(n as f64) + self.table[self.index(i + 1, m, rest)],
+        }
+    }
+
+    /// entropy bits of password, whose layer switches are at most max switches
+    pub fn entropy(&self, max_switches: usize) -> f64 {
+        self.table[self.index(0, 0, max_switches.min(self.len))]
+    }
+
+    /// min layer switches, which keep entropy not less than min entropy
+    pub fn min_switches(&self, min_entropy: f64) -> Option<usize> {
+        (0..=self.len).find(|&k| self.entropy(k) >= min_entropy)
+    }
+
+    /// draw password uniformly, whose layer switches are at most max switches
+    #[cfg(feature = "std")]
+    pub fn sample(&self, max_switches: usize) -> anyhow::Result<Password> {
+        self.sample_with_rng(max_switches, &mut rand::thread_rng())
+    }
+
+    /// draw password uniformly, whose layer switches are at most max switches, with given CSPRNG
+    pub fn sample_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        max_switches: usize,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        let (mut layer, mut rest) = (0, max_switches.min(self.len));
+        let mut chars = Vec::with_capacity(self.len);
+        for i in 0..self.len {
+            let log_weights: Vec<_> =
+                (0..self.layers.len()).map(|m| self.log_weight(i, layer, rest, m)).collect();
+            let max = log_weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if max == f64::NEG_INFINITY {
+                Err(GeneratorError::EmptySymbol)?
+            }
+            // relative to the max weight, which is 1, so they do not overflow
+            let weights: Vec<_> = log_weights.iter().map(|w| libm::exp2(w - max)).collect();
+            let mut target = csp_rng.gen::<f64>() * weights.iter().sum::<f64>();
+            let next = (0..self.layers.len())
+                .filter(|&m| weights[m] > 0.)
+                .find(|&m| {
+                    target -= weights[m];
+                    target < 0.
+                })
+                .or_else(|| (0..self.layers.len()).rev().find(|&m| weights[m] > 0.))
+                .ok_or(GeneratorError::EmptySymbol)?;
+            if next != layer {
+                rest -= 1;
+                layer = next;
+            }
+            let candidates = &self.layers[layer];
+            chars.push(candidates[csp_rng.gen_range(0, candidates.len())]); // rand 0.7
+        }
+        Password::from_chars(&chars)
+    }
+}
+
+/// log2 of sum of values, whose log2 are given, without overflow
+fn log2_sum(log_values: &[f64]) -> f64 {
+    let max = log_values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if max == f64::NEG_INFINITY {
+        return max;
+    }
+    max + libm::log2(log_values.iter().map(|v| libm::exp2(v - max)).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layer_test() {
+        assert_eq!(KeyboardLayout::Us.layer('a'), Some(0));
+        assert_eq!(KeyboardLayout::Us.layer('A'), Some(1));
+        assert_eq!(KeyboardLayout::Us.layer('1'), Some(0));
+        assert_eq!(KeyboardLayout::Us.layer('!'), Some(1));
+        assert_eq!(KeyboardLayout::Azerty.layer('1'), Some(1));
+        assert_eq!(KeyboardLayout::Azerty.layer('@'), Some(2));
+        assert_eq!(KeyboardLayout::Ios.layer('#'), Some(3));
+        assert_eq!(KeyboardLayout::Us.layer('あ'), None);
+    }
+
+    #[test]
+    fn all_candidate_mark_typeable_test() {
+        for layout in KeyboardLayout::ALL.iter() {
+            assert_eq!(layout.name().parse::<KeyboardLayout>().unwrap(), *layout);
+            let candidate: Vec<_> = super::super::MarkSet::CANDIDATE_MARK.chars().collect();
+            assert!(layout.untypeable(&candidate).is_empty(), "{:?}", layout);
+        }
+        assert!("dvorak".parse::<KeyboardLayout>().is_err());
+    }
+
+    #[test]
+    fn switches_test() {
+        let chars: Vec<_> = "abcDEF1!a".chars().collect();
+        assert_eq!(KeyboardLayout::Us.switches(&chars), 4);
+        assert_eq!(KeyboardLayout::Ios.switches(&chars), 3);
+        assert_eq!(KeyboardLayout::Us.switches(&['a', 'b']), 0);
+    }
+
+    #[test]
+    fn entropy_test() {
+        let chars: Vec<_> = format!("{}{}", LOWER, UPPER).chars().collect();
+        let sampler = SwitchSampler::new(KeyboardLayout::Us, 10, &chars);
+        assert!((sampler.entropy(10) - 10. * 52f64.log2()).abs() < 1e-9);
+        assert!((sampler.entropy(0) - 10. * 26f64.log2()).abs() < 1e-9);
+        assert_eq!(sampler.min_switches(0.), Some(0));
+        assert_eq!(sampler.min_switches(1000.), None);
+    }
+
+    #[test]
+    fn sample_test() {
+        let chars: Vec<_> = format!("{}{}{}", LOWER, UPPER, "!@#").chars().collect();
+        let sampler = SwitchSampler::new(KeyboardLayout::Ios, 20, &chars);
+        for max_switches in 0..4 {
+            for _ in 0..100 {
+                let password = sampler.sample(max_switches).unwrap();
+                assert_eq!(password.len(), 20);
+                assert!(KeyboardLayout::Ios.switches(password.iter()) <= max_switches);
+            }
+        }
+    }
+
+    #[test]
+    fn long_large_alphabet_test() {
+        let cjk = (0x4e00..0x4e00 + 1000).filter_map(core::char::from_u32);
+        let chars: Vec<_> =
+            format!("{}{}{}!@#", LOWER, UPPER, NUMERIC).chars().chain(cjk).collect();
+        let sampler = SwitchSampler::new(KeyboardLayout::Us, 128, &chars);
+        let entropy = sampler.entropy(128);
+        assert!((entropy - 128. * (chars.len() as f64).log2()).abs() < 1e-6, "{}", entropy);
+        assert!(sampler.entropy(0).is_finite());
+        for max_switches in [0, 3, 128] {
+            let password = sampler.sample(max_switches).unwrap();
+            assert_eq!(password.len(), 128);
+            assert!(KeyboardLayout::Us.switches(password.iter()) <= max_switches);
+        }
+        let sampler = SwitchSampler::new(KeyboardLayout::Us, 8, &[]);
+        assert_eq!(sampler.entropy(8), f64::NEG_INFINITY);
+        assert!(sampler.sample(8).is_err());
+    }
+}
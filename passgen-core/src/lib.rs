@@ -0,0 +1,303 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod api_token;
+pub mod escape;
+pub mod filter;
+pub mod generator;
+#[cfg(feature = "hash")]
+pub mod hash;
+pub mod keyboard;
+pub mod otp;
+pub mod password;
+pub mod phonetic;
+pub mod phrase;
+pub mod pin;
+#[cfg(feature = "qr")]
+pub mod qr;
+pub mod symbol;
+pub mod token;
+#[cfg(feature = "vault")]
+pub mod vault;
+
+pub use api_token::*;
+pub use escape::*;
+pub use filter::*;
+pub use generator::*;
+#[cfg(feature = "hash")]
+pub use hash::*;
+pub use keyboard::*;
+pub use otp::*;
+pub use password::*;
+pub use phonetic::*;
+pub use phrase::*;
+pub use pin::*;
+#[cfg(feature = "qr")]
+pub use qr::*;
+pub use symbol::*;
+pub use token::*;
+#[cfg(feature = "vault")]
+pub use vault::*;
+
+use alloc::string::String;
+use thiserror;
+
+/// min length of password is 8 for convenience
+pub const PASSWORD_MIN_LENGTH: usize = 8;
+
+/// default length of password is 20
+pub const PASSWORD_DEFAULT_LENGTH: usize = 20;
+
+/// max length of password is 128 for convenience such as GUI
+pub const PASSWORD_MAX_LENGTH: usize = 128;
+
+/// the size of array must be known at compile time, so we have set it to 1024
+pub const PASSWORD_BUFFER_SIZE: usize = 1024;
+
+/// generator gives up after redrawing this many times because of word filter
+pub const PASSWORD_MAX_REDRAW: usize = 1000;
+
+/// generator gives up after drawing this many passwords lacking some class, which is rare
+/// unless selected classes barely fit in the length
+pub const PASSWORD_MAX_INVALID_DRAW: usize = 100_000;
+
+/// tokens of context string shorter than this are allowed to appear in password
+pub const CONTEXT_MIN_TOKEN_LENGTH: usize = 3;
+
+/// password is zero-filled at dropped time
+pub const PASSWORD_FILL_CHARACTER: char = '0';
+
+/// serialize enums by its name such as "us", and deserialize them by `FromStr`
+#[cfg(feature = "serde")]
+macro_rules! serde_by_name {
+    ($($t:ty),*) => {$(
+        impl serde::Serialize for $t {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.name())
+            }
+        }
+        impl<'de> serde::Deserialize<'de> for $t {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = <String as serde::Deserialize>::deserialize(deserializer)?;
+                name.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+#[cfg(feature = "serde")]
+serde_by_name!(KeyboardLayout, UnicodeClass, LengthUnit);
+
+#[derive(Debug, thiserror::Error)]
+pub enum GeneratorError {
+    #[error("password should not be empty")]
+    EmptyLength,
+
+    #[error("password should be longer than {PASSWORD_MIN_LENGTH}, but given is {0}")]
+    TooShortLength(usize),
+
+    #[error(
+        "password max length is {PASSWORD_MAX_LENGTH}, for convenience such as GUI, but given is {0}"
+    )]
+    TooLongLength(usize),
+
+    #[error("because no available symbol, cannot generate a password")]
+    EmptySymbol,
+
+    #[error("password was redrawn {0} times, but all of them lacked some class or contained filtered word or context")]
+    TooManyRedraw(usize),
+
+    #[error("password entropy is {0:.1} bits, but it should be at least {1:.1} bits")]
+    LowEntropy(f64, f64),
+
+    #[error(
+        "password needs at least {0} layer switches to use all selected characters, but max is {1}"
+    )]
+    TooFewSwitches(usize, usize),
+
+    #[error("{0:?} is control, format, unassigned or combining character, so cannot be used")]
+    InvalidChar(char),
+
+    #[error("keyboard layout cannot be used with length in bytes")]
+    KeyboardWithBytes,
+
+    #[error("password needs length {0} to contain every selected class, but given is {1}")]
+    TooManyClasses(usize, usize),
+}
+
+/// return kind of error, which is the name of variant such as "TooShortLength"
+pub fn error_kind(err: &anyhow::Error) -> &'static str {
+    match err.downcast_ref::<GeneratorError>() {
+        Some(GeneratorError::EmptyLength) => "EmptyLength",
+        Some(GeneratorError::TooShortLength(_)) => "TooShortLength",
+        Some(GeneratorError::TooLongLength(_)) => "TooLongLength",
+        Some(GeneratorError::EmptySymbol) => "EmptySymbol",
+        Some(GeneratorError::TooManyRedraw(_)) => "TooManyRedraw",
+        Some(GeneratorError::LowEntropy(_, _)) => "LowEntropy",
+        Some(GeneratorError::TooFewSwitches(_, _)) => "TooFewSwitches",
+        Some(GeneratorError::InvalidChar(_)) => "InvalidChar",
+        Some(GeneratorError::KeyboardWithBytes) => "KeyboardWithBytes",
+        Some(GeneratorError::TooManyClasses(_, _)) => "TooManyClasses",
+        None if err.is::<PasswordError>() => "TooLongLength",
+        None => "Unknown",
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PasswordError {
+    #[error("max password length is {PASSWORD_BUFFER_SIZE}, but required length is {0}")]
+    TooLongLength(usize),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PhraseError {
+    #[error("passphrase should have at least {PHRASE_MIN_WORDS} words, but given is {0}")]
+    TooFewWords(usize),
+
+    #[error("passphrase max words is {PHRASE_MAX_WORDS}, but given is {0}")]
+    TooManyWords(usize),
+
+    #[error("separator of words should be a character or empty, but given is {0:?}")]
+    LongSeparator(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PinError {
+    #[error("PIN should have at least {PIN_MIN_LENGTH} digits, but given is {0}")]
+    TooShortLength(usize),
+
+    #[error("PIN max length is {PIN_MAX_LENGTH}, but given is {0}")]
+    TooLongLength(usize),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TokenError {
+    #[error("token should not be empty")]
+    EmptySize,
+
+    #[error("token max size is {TOKEN_MAX_BYTES} bytes, but given is {0} bytes")]
+    TooManyBytes(usize),
+
+    #[error("token max size is {TOKEN_MAX_BITS} bits, but given is {0} bits")]
+    TooManyBits(usize),
+
+    #[error("token body entropy is {0:.1} bits, but it should be at least {API_TOKEN_MIN_BODY_ENTROPY:.1} bits")]
+    LowBodyEntropy(f64),
+
+    #[error("token does not start with the prefix")]
+    InvalidPrefix,
+
+    #[error("token length should be {0}, but given is {1}")]
+    InvalidLength(usize, usize),
+
+    #[error("{0:?} is not in the alphabet of token body")]
+    InvalidChar(char),
+
+    #[error("checksum of token does not match")]
+    InvalidChecksum,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum OtpError {
+    #[error("otp secret should not be empty")]
+    EmptySecret,
+
+    #[error("otp secret should be at least {OTP_MIN_SECRET_BYTES} bytes, but given is {0} bytes")]
+    ShortSecret(usize),
+
+    #[error("{0:?} is not base32 character, so otp secret cannot be decoded")]
+    InvalidSecret(char),
+
+    #[error("otp digits should be from 6 to 8, but given is {0}")]
+    InvalidDigits(u32),
+
+    #[error("otp period should not be zero")]
+    ZeroPeriod,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum HashError {
+    #[error("hash is not format of sha512-crypt, bcrypt, argon2id or pbkdf2-sha256")]
+    UnknownFormat,
+
+    #[error("bcrypt ignores password longer than 72 bytes, but given is {0} bytes")]
+    TooLongForBcrypt(usize),
+
+    #[error("rounds of sha512-crypt should be from 1000 to 999999999, but given is {0}")]
+    InvalidRounds(usize),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum VaultError {
+    #[error("file is not passgen vault, or it is broken")]
+    InvalidFormat,
+
+    #[error("vault version {0} is not supported, update passgen")]
+    UnsupportedVersion(u8),
+
+    #[error("cannot decrypt vault, passphrase is wrong or vault is tampered")]
+    DecryptionFailed,
+
+    #[error("cannot encrypt vault")]
+    EncryptionFailed,
+
+    #[error("path of vault should be file")]
+    InvalidPath,
+
+    #[error("name of vault entry should not be empty")]
+    EmptyName,
+
+    #[error("vault entry {0:?} already exists, rotate it instead")]
+    EntryExists(String),
+
+    #[error("vault entry {0:?} is not found")]
+    EntryNotFound(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum WifiError {
+    #[error("ssid of Wi-Fi network should not be empty")]
+    EmptySsid,
+
+    #[error("WPA passphrase should be from 8 to 63 characters, but given is {0} characters")]
+    InvalidPassphraseLength(usize),
+
+    #[error("{0:?} cannot be used in WPA passphrase, which is printable ASCII only")]
+    InvalidPassphraseChar(char),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ParseError {
+    #[error("unknown mark preset: {0}")]
+    UnknownPreset(String),
+
+    #[error("unknown escape: {0}")]
+    UnknownEscape(String),
+
+    #[error("unknown keyboard layout: {0}")]
+    UnknownLayout(String),
+
+    #[error("unknown unicode class: {0}")]
+    UnknownUnicodeClass(String),
+
+    #[error("unknown length unit: {0}")]
+    UnknownLengthUnit(String),
+
+    #[error("unknown encoding: {0}")]
+    UnknownEncoding(String),
+
+    #[error("unknown checksum: {0}")]
+    UnknownChecksum(String),
+
+    #[error("unknown otp algorithm: {0}")]
+    UnknownOtpAlgorithm(String),
+
+    #[error("unknown Wi-Fi security: {0}")]
+    UnknownWifiSecurity(String),
+
+    #[error("unknown hash scheme: {0}")]
+    UnknownHashScheme(String),
+
+    #[error("unknown reading: {0}")]
+    UnknownReading(String),
+}
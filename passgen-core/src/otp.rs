@@ -0,0 +1,413 @@
+use alloc::{format, string::String, string::ToString, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+use hmac::{digest::KeyInit, Hmac, Mac};
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    escape::Escape,
+    password::{fill_volatile, zero_string, Password},
+    token::Encoding,
+    OtpError, ParseError, PASSWORD_FILL_CHARACTER,
+};
+
+/// default size of secret is 20 bytes, which is recommended by RFC 4226
+pub const OTP_DEFAULT_SECRET_BYTES: usize = 20;
+
+/// min size of secret is 16 bytes, which is required by RFC 4226
+pub const OTP_MIN_SECRET_BYTES: usize = 16;
+
+/// default digits of code
+pub const OTP_DEFAULT_DIGITS: u32 = 6;
+
+/// default time step of TOTP is 30 seconds
+pub const OTP_DEFAULT_PERIOD: u64 = 30;
+
+/// hash algorithm of HMAC
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OtpAlgorithm {
+    #[default]
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl FromStr for OtpAlgorithm {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|algorithm| algorithm.name() == s.to_uppercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownOtpAlgorithm(s.to_string()))
+    }
+}
+
+impl OtpAlgorithm {
+    pub const ALL: [OtpAlgorithm; 3] =
+        [OtpAlgorithm::Sha1, OtpAlgorithm::Sha256, OtpAlgorithm::Sha512];
+
+    /// return name of this algorithm used in otpauth URI, such as "SHA1"
+    pub fn name(&self) -> &'static str {
+        match self {
+            OtpAlgorithm::Sha1 => "SHA1",
+            OtpAlgorithm::Sha256 => "SHA256",
+            OtpAlgorithm::Sha512 => "SHA512",
+        }
+    }
+
+    /// return HMAC of message with key
+    pub fn hmac(&self, key: &[u8], message: &[u8]) -> Vec<u8> {
+        fn hmac<M: Mac + KeyInit>(key: &[u8], message: &[u8]) -> Vec<u8> {
+            let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC can take key of any size");
+            mac.update(message);
+            mac.finalize().into_bytes().to_vec()
+        }
+        match self {
+            OtpAlgorithm::Sha1 => hmac::<Hmac<sha1::Sha1>>(key, message),
+            OtpAlgorithm::Sha256 => hmac::<Hmac<sha2::Sha256>>(key, message),
+            OtpAlgorithm::Sha512 => hmac::<Hmac<sha2::Sha512>>(key, message),
+        }
+    }
+}
+
+/// kind of one time password, counter based HOTP or time based TOTP
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OtpKind {
+    Hotp(u64),
+    Totp,
+}
+
+/// RFC 4226 HOTP and RFC 6238 TOTP secret and its parameters.
+/// secret is zero-filled at dropped time
+#[derive(Clone, PartialEq)]
+pub struct Otp {
+    secret: Vec<u8>,
+    pub algorithm: OtpAlgorithm,
+    digits: u32,
+    period: u64,
+}
+
+impl Drop for Otp {
+    fn drop(&mut self) {
+        fill_volatile(&mut self.secret, 0);
+    }
+}
+
+impl fmt::Debug for Otp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Otp")
+            .field("secret", &"********")
+            .field("algorithm", &self.algorithm)
+            .field("digits", &self.digits)
+            .field("period", &self.period)
+            .finish()
+    }
+}
+
+impl Otp {
+    /// make otp from secret bytes, with default parameters
+    pub fn new(secret: &[u8]) -> anyhow::Result<Self> {
+        let otp = Self {
+            secret: secret.to_vec(),
+            algorithm: OtpAlgorithm::default(),
+            digits: OTP_DEFAULT_DIGITS,
+            period: OTP_DEFAULT_PERIOD,
+        };
+        otp.validate()?;
+        Ok(otp)
+    }
+
+    /// make otp from base32 secret, case-insensitively, ignoring spaces and padding
+    pub fn from_base32(secret: &str) -> anyhow::Result<Self> {
+        let mut bytes = decode_base32(secret)?;
+        let otp = Self::new(&bytes);
+        fill_volatile(&mut bytes, 0);
+        otp
+    }
+
+    /// generate new secret of given bytes with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate(bytes: usize) -> anyhow::Result<Self> {
+        Self::generate_with_rng(bytes, &mut rand::thread_rng())
+    }
+
+    /// generate new secret of given bytes with given CSPRNG
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        bytes: usize,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Self> {
+        if bytes < OTP_MIN_SECRET_BYTES {
+            Err(OtpError::ShortSecret(bytes))?
+        }
+        let mut secret = vec![0u8; bytes];
+        csp_rng.fill_bytes(&mut secret);
+        let otp = Self::new(&secret);
+        fill_volatile(&mut secret, 0);
+        otp
+    }
+
+    /// return Ok if parameters are valid, or its error
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.secret.is_empty() {
+            Err(OtpError::EmptySecret)?
+        }
+        if !(6..=8).contains(&self.digits) {
+            Err(OtpError::InvalidDigits(self.digits))?
+        }
+        if self.period == 0 {
+            Err(OtpError::ZeroPeriod)?
+        }
+        Ok(())
+    }
+
+    /// return the number of digits of code
+    pub fn digits(&self) -> u32 {
+        self.digits
+    }
+
+    /// set the number of digits of code, which should be from 6 to 8
+    pub fn set_digits(&mut self, digits: u32) -> anyhow::Result<()> {
+        if !(6..=8).contains(&digits) {
+            Err(OtpError::InvalidDigits(digits))?
+        }
+        self.digits = digits;
+        Ok(())
+    }
+
+    /// return seconds of TOTP time step
+    pub fn period(&self) -> u64 {
+        self.period
+    }
+
+    /// set seconds of TOTP time step, which should not be zero
+    pub fn set_period(&mut self, period: u64) -> anyhow::Result<()> {
+        if period == 0 {
+            Err(OtpError::ZeroPeriod)?
+        }
+        self.period = period;
+        Ok(())
+    }
+
+    /// return the number of bytes of secret
+    pub fn secret_len(&self) -> usize {
+        self.secret.len()
+    }
+
+    /// return secret in base32 without padding, as authenticator apps
+    pub fn secret_base32(&self) -> anyhow::Result<Password> {
+        let mut encoded = Encoding::Base32.encode(&self.secret, false);
+        let secret = Password::from_chars(&encoded);
+        fill_volatile(&mut encoded, PASSWORD_FILL_CHARACTER);
+        secret
+    }
+
+    /// return RFC 4226 HOTP code of counter
+    pub fn hotp(&self, counter: u64) -> String {
+        let hash = self.algorithm.hmac(&self.secret, &counter.to_be_bytes());
+        // dynamic truncation
+        let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            hash[offset] & 0x7f,
+            hash[offset + 1],
+            hash[offset + 2],
+            hash[offset + 3],
+        ]);
+        let code = binary % 10u32.pow(self.digits);
+        format!("{:0width$}", code, width = self.digits as usize)
+    }
+
+    /// return counter of TOTP at unix time
+    pub fn counter(&self, unix_time: u64) -> u64 {
+        unix_time / self.period
+    }
+
+    /// return seconds until the next TOTP code at unix time
+    pub fn remaining(&self, unix_time: u64) -> u64 {
+        self.period - unix_time % self.period
+    }
+
+    /// return RFC 6238 TOTP code at unix time
+    pub fn totp(&self, unix_time: u64) -> String {
+        self.hotp(self.counter(unix_time))
+    }
+
+    /// return true if code matches TOTP at unix time, allowing `skew` steps before and after
+    pub fn verify_totp(&self, code: &str, unix_time: u64, skew: u64) -> bool {
+        let counter = self.counter(unix_time);
+        let counters = counter.saturating_sub(skew)..=counter.saturating_add(skew);
+        // compare all codes, in constant time for each code
+        counters.fold(false, |verified, counter| {
+            constant_time_eq(self.hotp(counter).as_bytes(), code.as_bytes()) | verified
+        })
+    }
+
+    /// return otpauth URI, which can be imported by authenticator apps such as QR code
+    pub fn uri(&self, kind: OtpKind, issuer: &str, account: &str) -> anyhow::Result<Password> {
+        let label = match issuer {
+            "" => Escape::Url.escape(account),
+            _ => format!("{}:{}", Escape::Url.escape(issuer), Escape::Url.escape(account)),
+        };
+        let mut uri = match kind {
+            OtpKind::Hotp(_) => format!("otpauth://hotp/{}?secret=", label),
+            OtpKind::Totp => format!("otpauth://totp/{}?secret=", label),
+        };
+        uri.extend(self.secret_base32()?.iter());
+        if !issuer.is_empty() {
+            uri.push_str(&format!("&issuer={}", Escape::Url.escape(issuer)));
+        }
+        uri.push_str(&format!("&algorithm={}&digits={}", self.algorithm.name(), self.digits));
+        match kind {
+            OtpKind::Hotp(counter) => uri.push_str(&format!("&counter={}", counter)),
+            OtpKind::Totp => uri.push_str(&format!("&period={}", self.period)),
+        }
+        let mut chars: Vec<char> = uri.chars().collect();
+        let password = Password::from_chars(&chars);
+        fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+        // uri contains secret, so it is zero-filled too
+        zero_string(&mut uri);
+        password
+    }
+}
+
+/// return current unix time in seconds
+#[cfg(feature = "std")]
+pub fn unix_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system time should be after unix epoch")
+        .as_secs()
+}
+
+/// compare bytes without early return, to avoid timing attack
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// decode RFC 4648 base32 case-insensitively, ignoring spaces, hyphens and padding
+fn decode_base32(encoded: &str) -> anyhow::Result<Vec<u8>> {
+    let alphabet = Encoding::Base32.alphabet();
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let (mut buffer, mut buffered) = (0u32, 0);
+    for c in encoded.chars().filter(|c| !c.is_whitespace() && *c != '-' && *c != '=') {
+        let value = alphabet.find(c.to_ascii_uppercase()).ok_or(OtpError::InvalidSecret(c))?;
+        buffer = (buffer << 5) | value as u32;
+        buffered += 5;
+        if buffered >= 8 {
+            buffered -= 8;
+            bytes.push((buffer >> buffered) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    /// secrets of RFC 6238 test vectors, for each algorithm
+    fn rfc6238_secret(algorithm: OtpAlgorithm) -> Otp {
+        let secret = match algorithm {
+            OtpAlgorithm::Sha1 => &b"12345678901234567890"[..],
+            OtpAlgorithm::Sha256 => &b"12345678901234567890123456789012"[..],
+            OtpAlgorithm::Sha512 => {
+                &b"1234567890123456789012345678901234567890123456789012345678901234"[..]
+            }
+        };
+        let mut otp = Otp::new(secret).unwrap();
+        otp.algorithm = algorithm;
+        otp.set_digits(8).unwrap();
+        otp
+    }
+
+    #[test]
+    fn rfc4226_test() {
+        let otp = Otp::new(b"12345678901234567890").unwrap();
+        let codes = [
+            "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583",
+            "399871", "520489",
+        ];
+        for (counter, code) in codes.iter().enumerate() {
+            assert_eq!(otp.hotp(counter as u64), *code);
+        }
+    }
+
+    #[test]
+    fn rfc6238_test() {
+        let vectors = [
+            (59, ["94287082", "46119246", "90693936"]),
+            (1111111109, ["07081804", "68084774", "25091201"]),
+            (1111111111, ["14050471", "67062674", "99943326"]),
+            (1234567890, ["89005924", "91819424", "93441116"]),
+            (2000000000, ["69279037", "90698825", "38618901"]),
+            (20000000000, ["65353130", "77737706", "47863826"]),
+        ];
+        for (time, codes) in vectors.iter() {
+            for (algorithm, code) in OtpAlgorithm::ALL.iter().zip(codes.iter()) {
+                assert_eq!(rfc6238_secret(*algorithm).totp(*time), *code);
+            }
+        }
+    }
+
+    #[test]
+    fn verify_totp_test() {
+        let otp = rfc6238_secret(OtpAlgorithm::Sha1);
+        assert!(otp.verify_totp("07081804", 1111111109, 0));
+        assert!(!otp.verify_totp("07081804", 1111111109 + 30, 0));
+        assert!(otp.verify_totp("07081804", 1111111109 + 30, 1));
+        assert!(!otp.verify_totp("0708180", 1111111109, 1));
+        assert_eq!(otp.remaining(59), 1);
+        assert_eq!(otp.remaining(60), 30);
+    }
+
+    #[test]
+    fn base32_test() {
+        let otp = Otp::new(b"12345678901234567890").unwrap();
+        let secret = otp.secret_base32().unwrap().to_string();
+        assert_eq!(secret, "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ");
+        let decoded = Otp::from_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq").unwrap();
+        assert_eq!(decoded, otp);
+        assert!(Otp::from_base32("GEZDGNBVGY3TQOJ1").is_err());
+        assert!(Otp::from_base32("").is_err());
+    }
+
+    #[test]
+    fn uri_test() {
+        let otp = Otp::new(b"12345678901234567890").unwrap();
+        let uri = otp.uri(OtpKind::Totp, "Acme Co", "alice@example.com").unwrap().to_string();
+        assert_eq!(
+            uri,
+            "otpauth://totp/Acme%20Co:alice%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+            &issuer=Acme%20Co&algorithm=SHA1&digits=6&period=30"
+        );
+        let uri = otp.uri(OtpKind::Hotp(3), "", "bob").unwrap().to_string();
+        assert_eq!(
+            uri,
+            "otpauth://hotp/bob?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ&algorithm=SHA1&digits=6&counter=3"
+        );
+    }
+
+    #[test]
+    fn generate_test() {
+        let otp = Otp::generate(OTP_DEFAULT_SECRET_BYTES).unwrap();
+        assert_eq!(otp.secret_base32().unwrap().len(), 32);
+        assert!(Otp::generate(10).is_err());
+        let a = Otp::generate_with_rng(20, &mut StdRng::seed_from_u64(0)).unwrap();
+        let b = Otp::generate_with_rng(20, &mut StdRng::seed_from_u64(0)).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(format!("{:?}", a).matches("********").count(), 1);
+    }
+
+    #[test]
+    fn invalid_parameter_test() {
+        let mut otp = Otp::new(b"12345678901234567890").unwrap();
+        assert!(otp.set_digits(9).is_err());
+        assert!(otp.set_digits(10).is_err());
+        assert!(otp.set_period(0).is_err());
+        assert_eq!((otp.digits(), otp.period()), (OTP_DEFAULT_DIGITS, OTP_DEFAULT_PERIOD));
+        assert!(otp.validate().is_ok());
+        assert!("sha3".parse::<OtpAlgorithm>().is_err());
+        assert_eq!("sha256".parse::<OtpAlgorithm>().unwrap(), OtpAlgorithm::Sha256);
+    }
+}
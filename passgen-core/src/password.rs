@@ -0,0 +1,278 @@
+use super::{ParseError, PasswordError, PASSWORD_BUFFER_SIZE, PASSWORD_FILL_CHARACTER};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use anyhow;
+use core::{
+    fmt, ptr,
+    str::FromStr,
+    sync::atomic::{compiler_fence, Ordering},
+};
+use itertools;
+use rand::{CryptoRng, Rng, RngCore};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// unit of password length, for systems which count length in graphemes or bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LengthUnit {
+    #[default]
+    Chars,
+    Graphemes,
+    Bytes,
+}
+
+impl FromStr for LengthUnit {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|unit| unit.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownLengthUnit(s.to_string()))
+    }
+}
+
+impl LengthUnit {
+    pub const ALL: [LengthUnit; 3] = [LengthUnit::Chars, LengthUnit::Graphemes, LengthUnit::Bytes];
+
+    /// return name of this unit, such as "bytes"
+    pub fn name(&self) -> &'static str {
+        match self {
+            LengthUnit::Chars => "chars",
+            LengthUnit::Graphemes => "graphemes",
+            LengthUnit::Bytes => "bytes",
+        }
+    }
+
+    /// return length of password in this unit
+    pub fn measure(&self, password: &Password) -> usize {
+        match self {
+            LengthUnit::Chars => password.len(),
+            LengthUnit::Graphemes => password.to_string().graphemes(true).count(),
+            LengthUnit::Bytes => password.iter().map(|c| c.len_utf8()).sum(),
+        }
+    }
+}
+
+pub struct Password {
+    len: usize,
+    password: [char; PASSWORD_BUFFER_SIZE],
+}
+
+impl Drop for Password {
+    fn drop(&mut self) {
+        let len = self.len();
+        fill_volatile(&mut self.password[..len], PASSWORD_FILL_CHARACTER);
+    }
+}
+
+impl fmt::Display for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", vec!['*'; 8].into_iter().collect::<String>())
+    }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string())
+    }
+}
+
+impl Password {
+    /// generate new password, from given chars, with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate(len: usize, chars: &[char]) -> anyhow::Result<Self> {
+        Self::generate_with_rng(len, chars, &mut rand::thread_rng())
+    }
+
+    /// generate new password, from given chars, with given CSPRNG such as hardware RNG
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(
+        len: usize,
+        chars: &[char],
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Self> {
+        if len > PASSWORD_BUFFER_SIZE {
+            Err(PasswordError::TooLongLength(len))?
+        }
+        let mut password = [PASSWORD_FILL_CHARACTER; PASSWORD_BUFFER_SIZE];
+        let indices = (0..len).map(|_| csp_rng.gen_range(0, chars.len())); // rand 0.7
+        for (i, j) in itertools::zip(0..len, indices) {
+            password[i] = chars[j];
+        }
+        Ok(Self { len, password })
+    }
+
+    /// generate new password, which is at most given bytes in UTF-8, from given chars, with CSPRNG.
+    /// each character is drawn from chars which fit in the rest bytes
+    #[cfg(feature = "std")]
+    pub fn generate_bytes(bytes: usize, chars: &[char]) -> anyhow::Result<Self> {
+        Self::generate_bytes_with_rng(bytes, chars, &mut rand::thread_rng())
+    }
+
+    /// generate new password, which is at most given bytes in UTF-8, with given CSPRNG
+    pub fn generate_bytes_with_rng<R: RngCore + CryptoRng>(
+        bytes: usize,
+        chars: &[char],
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Self> {
+        if bytes > PASSWORD_BUFFER_SIZE {
+            Err(PasswordError::TooLongLength(bytes))?
+        }
+        let mut password = [PASSWORD_FILL_CHARACTER; PASSWORD_BUFFER_SIZE];
+        let (mut len, mut rest) = (0, bytes);
+        loop {
+            let fit: Vec<_> = chars.iter().filter(|c| c.len_utf8() <= rest).collect();
+            if fit.is_empty() {
+                break;
+            }
+            password[len] = *fit[csp_rng.gen_range(0, fit.len())]; // rand 0.7
+            rest -= password[len].len_utf8();
+            len += 1;
+        }
+        Ok(Self { len, password })
+    }
+
+    /// make password from given chars as it is, such as encoded token
+    pub fn from_chars(chars: &[char]) -> anyhow::Result<Self> {
+        if chars.len() > PASSWORD_BUFFER_SIZE {
+            Err(PasswordError::TooLongLength(chars.len()))?
+        }
+        let mut password = [PASSWORD_FILL_CHARACTER; PASSWORD_BUFFER_SIZE];
+        password[..chars.len()].copy_from_slice(chars);
+        Ok(Self { len: chars.len(), password })
+    }
+
+    #[inline]
+    /// return password length
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// if return &str, we have to do some allocations, so there is no choice but to return String
+    pub fn to_string(&self) -> String {
+        self.password.iter().take(self.len()).collect()
+    }
+
+    /// return iterator for password
+    pub fn iter(&self) -> impl Iterator<Item = &char> {
+        self.password.iter().take(self.len())
+    }
+}
+
+/// overwrite secret with fill, and prevent compiler from eliding it as dead store before drop
+pub fn fill_volatile<T: Copy>(secret: &mut [T], fill: T) {
+    for item in secret.iter_mut() {
+        unsafe { ptr::write_volatile(item, fill) };
+    }
+    compiler_fence(Ordering::SeqCst);
+}
+
+/// zero-fill bytes of string and empty it, so its buffer holds no secret when freed.
+/// buffer left by reallocation is never zeroed, so secret should be built in exact capacity
+pub fn zero_string(secret: &mut String) {
+    // zero bytes are valid UTF-8, so string is kept valid
+    fill_volatile(unsafe { secret.as_bytes_mut() }, 0);
+    secret.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+
+    use super::super::symbol;
+    use super::*;
+
+    #[test]
+    fn generate_test() {
+        let a = Password::generate(10, &['a']).unwrap();
+        assert_eq!(format!("{}", a), "********");
+        assert_eq!(format!("{:?}", a), "aaaaaaaaaa");
+    }
+
+    #[test]
+    fn generate_uniqueness_test() {
+        let chars = format!("{}{}{}", symbol::LOWER, symbol::UPPER, symbol::NUMERIC);
+        let mut set = HashSet::new();
+        for _ in 0..10000 {
+            set.insert(
+                Password::generate(20, &chars.chars().collect::<Vec<_>>()).unwrap().to_string(),
+            );
+        }
+        assert_eq!(set.len(), 10000);
+    }
+
+    #[test]
+    fn generate_with_rng_test() {
+        let chars: Vec<_> = symbol::LOWER.chars().collect();
+        let a = Password::generate_with_rng(20, &chars, &mut StdRng::seed_from_u64(0)).unwrap();
+        let b = Password::generate_with_rng(20, &chars, &mut StdRng::seed_from_u64(0)).unwrap();
+        assert_eq!(a.to_string(), b.to_string());
+        let c = Password::generate_with_rng(20, &chars, &mut StdRng::seed_from_u64(1)).unwrap();
+        assert_ne!(a.to_string(), c.to_string());
+    }
+
+    #[test]
+    fn drop_test() {
+        let raw: *const _;
+        {
+            let mut password = ['0'; 1024];
+            password[0] = 'a';
+            password[1] = 'b';
+            password[2] = 'c';
+            let password = Password { len: 3, password };
+            raw = &password.password;
+            assert_eq!(unsafe { &*raw }.clone()[..3], ['a', 'b', 'c']);
+        }
+        assert_eq!(unsafe { &*raw }.clone()[..3], ['0', '0', '0']);
+    }
+
+    #[test]
+    fn zero_string_test() {
+        let mut secret = "p@ss".to_string();
+        let raw = secret.as_ptr();
+        zero_string(&mut secret);
+        assert!(secret.is_empty());
+        assert_eq!(unsafe { core::slice::from_raw_parts(raw, 4) }, [0; 4]);
+    }
+
+    #[test]
+    fn from_chars_test() {
+        let password = Password::from_chars(&['a', 'b', 'c']).unwrap();
+        assert_eq!(password.len(), 3);
+        assert_eq!(format!("{:?}", password), "abc");
+        assert!(Password::from_chars(&['a'; 1025]).is_err());
+    }
+
+    #[test]
+    fn generate_bytes_test() {
+        let password = Password::generate_bytes(10, &['a', 'é', '😀']).unwrap();
+        assert_eq!(LengthUnit::Bytes.measure(&password), 10);
+        let password = Password::generate_bytes(10, &['😀']).unwrap();
+        assert_eq!(format!("{:?}", password), "😀😀");
+    }
+
+    #[test]
+    fn length_unit_test() {
+        let password = Password::from_chars(&['a', 'e', '\u{0301}', 'ア', '😀']).unwrap();
+        assert_eq!(LengthUnit::Chars.measure(&password), 5);
+        assert_eq!(LengthUnit::Graphemes.measure(&password), 4);
+        assert_eq!(LengthUnit::Bytes.measure(&password), 1 + 1 + 2 + 3 + 4);
+        assert_eq!("Bytes".parse::<LengthUnit>().unwrap(), LengthUnit::Bytes);
+        assert!("words".parse::<LengthUnit>().is_err());
+    }
+
+    #[test]
+    fn too_long_password_test() {
+        let valid = Password::generate(1024, &['a', 'b', 'c']);
+        assert!(valid.is_ok());
+        let invalid = Password::generate(1025, &['a', 'b', 'c']);
+        assert!(invalid.is_err());
+        assert_eq!(
+            invalid.unwrap_err().to_string(),
+            "max password length is 1024, but required length is 1025",
+        )
+    }
+}
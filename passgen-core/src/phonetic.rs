@@ -0,0 +1,247 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+use super::ParseError;
+
+/// password is read aloud in chunks of 4 characters by default
+pub const PHONETIC_DEFAULT_CHUNK: usize = 4;
+
+/// NATO/ICAO spelling alphabet, whose index is offset from 'a'
+pub const NATO_ALPHABET: [&str; 26] = [
+    "Alfa", "Bravo", "Charlie", "Delta", "Echo", "Foxtrot", "Golf", "Hotel", "India", "Juliett",
+    "Kilo", "Lima", "Mike", "November", "Oscar", "Papa", "Quebec", "Romeo", "Sierra", "Tango",
+    "Uniform", "Victor", "Whiskey", "X-ray", "Yankee", "Zulu",
+];
+
+/// names of digits, whose index is the digit
+pub const DIGIT_NAMES: [&str; 10] =
+    ["zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+/// English letter names in katakana, whose index is offset from 'a'
+pub const KANA_ALPHABET: [&str; 26] = [
+    "エー",
+    "ビー",
+    "シー",
+    "ディー",
+    "イー",
+    "エフ",
+    "ジー",
+    "エイチ",
+    "アイ",
+    "ジェー",
+    "ケー",
+    "エル",
+    "エム",
+    "エヌ",
+    "オー",
+    "ピー",
+    "キュー",
+    "アール",
+    "エス",
+    "ティー",
+    "ユー",
+    "ブイ",
+    "ダブリュー",
+    "エックス",
+    "ワイ",
+    "ゼット",
+];
+
+/// Japanese names of digits, whose index is the digit
+pub const KANA_DIGIT_NAMES: [&str; 10] =
+    ["ゼロ", "いち", "に", "さん", "よん", "ご", "ろく", "なな", "はち", "きゅう"];
+
+/// language of reading, such as NATO/ICAO spelling alphabet
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reading {
+    #[default]
+    Nato,
+    /// Japanese reading in kana, such as "大文字のエー"
+    Kana,
+}
+
+impl FromStr for Reading {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|reading| reading.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownReading(s.to_string()))
+    }
+}
+
+impl Reading {
+    pub const ALL: [Reading; 2] = [Reading::Nato, Reading::Kana];
+
+    /// return name of this reading, such as "nato"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reading::Nato => "nato",
+            Reading::Kana => "kana",
+        }
+    }
+
+    /// return the word to read character aloud, such as "capital Alfa" or "caret"
+    pub fn spell(&self, c: char) -> String {
+        match (self, c) {
+            (Reading::Nato, 'a'..='z') => NATO_ALPHABET[c as usize - 'a' as usize].to_lowercase(),
+            (Reading::Nato, 'A'..='Z') => {
+                format!("capital {}", NATO_ALPHABET[c as usize - 'A' as usize])
+            }
+            (Reading::Nato, '0'..='9') => DIGIT_NAMES[c as usize - '0' as usize].to_string(),
+            (Reading::Kana, 'a'..='z') => {
+                format!("小文字の{}", KANA_ALPHABET[c as usize - 'a' as usize])
+            }
+            (Reading::Kana, 'A'..='Z') => {
+                format!("大文字の{}", KANA_ALPHABET[c as usize - 'A' as usize])
+            }
+            (Reading::Kana, '0'..='9') => KANA_DIGIT_NAMES[c as usize - '0' as usize].to_string(),
+            (Reading::Kana, 'ぁ'..='ゖ') => format!("ひらがなの{}", c),
+            (Reading::Kana, 'ァ'..='ヺ') => format!("カタカナの{}", c),
+            _ => match self.mark_name(c) {
+                Some(name) => name.to_string(),
+                None => format!("{} U+{:04X}", c, c as u32),
+            },
+        }
+    }
+
+    /// return name of ASCII mark or space, such as "at sign"
+    pub fn mark_name(&self, c: char) -> Option<&'static str> {
+        let (nato, kana) = match c {
+            ' ' => ("space", "スペース"),
+            '!' => ("exclamation mark", "びっくりマーク"),
+            '"' => ("double quote", "ダブルクォート"),
+            '#' => ("hash", "シャープ"),
+            '$' => ("dollar sign", "ドル"),
+            '%' => ("percent sign", "パーセント"),
+            '&' => ("ampersand", "アンド"),
+            '\'' => ("single quote", "シングルクォート"),
+            '(' => ("left parenthesis", "左丸かっこ"),
+            ')' => ("right parenthesis", "右丸かっこ"),
+            '*' => ("asterisk", "アスタリスク"),
+            '+' => ("plus sign", "プラス"),
+            ',' => ("comma", "カンマ"),
+            '-' => ("hyphen", "ハイフン"),
+            '.' => ("period", "ピリオド"),
+            '/' => ("slash", "スラッシュ"),
+            ':' => ("colon", "コロン"),
+            ';' => ("semicolon", "セミコロン"),
+            '<' => ("less-than sign", "小なり"),
+            '=' => ("equals sign", "イコール"),
+            '>' => ("greater-than sign", "大なり"),
+            '?' => ("question mark", "はてなマーク"),
+            '@' => ("at sign", "アットマーク"),
+            '[' => ("left square bracket", "左角かっこ"),
+            '\\' => ("backslash", "バックスラッシュ"),
+            ']' => ("right square bracket", "右角かっこ"),
+            '^' => ("caret", "キャレット"),
+            '_' => ("underscore", "アンダースコア"),
+            '`' => ("backtick", "バッククォート"),
+            '{' => ("left curly brace", "左波かっこ"),
+            '|' => ("vertical bar", "縦棒"),
+            '}' => ("right curly brace", "右波かっこ"),
+            '~' => ("tilde", "チルダ"),
+            _ => return None,
+        };
+        match self {
+            Reading::Nato => Some(nato),
+            Reading::Kana => Some(kana),
+        }
+    }
+
+    /// return words to read each character of password aloud
+    pub fn spell_all(&self, password: &str) -> Vec<String> {
+        password.chars().map(|c| self.spell(c)).collect()
+    }
+
+    /// split password into chunks of given characters with their positions and words
+    pub fn chunks(&self, password: &str, size: usize) -> Vec<PhoneticChunk> {
+        let chars: Vec<char> = password.chars().collect();
+        chars
+            .chunks(size.max(1))
+            .enumerate()
+            .map(|(i, chunk)| PhoneticChunk {
+                position: i * size.max(1) + 1,
+                text: chunk.iter().collect(),
+                words: chunk.iter().map(|&c| self.spell(c)).collect(),
+            })
+            .collect()
+    }
+}
+
+/// chunk of password, which is read aloud at once
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhoneticChunk {
+    /// position of the first character, which starts from 1
+    pub position: usize,
+    pub text: String,
+    pub words: Vec<String>,
+}
+
+impl PhoneticChunk {
+    /// return position of the last character, which starts from 1
+    pub fn end(&self) -> usize {
+        self.position + self.text.chars().count() - 1
+    }
+}
+
+impl fmt::Display for PhoneticChunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{} {}: {}", self.position, self.end(), self.text, self.words.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spell_test() {
+        assert_eq!(Reading::Nato.spell('a'), "alfa");
+        assert_eq!(Reading::Nato.spell('X'), "capital X-ray");
+        assert_eq!(Reading::Nato.spell('9'), "nine");
+        assert_eq!(Reading::Nato.spell('^'), "caret");
+        assert_eq!(Reading::Nato.spell('@'), "at sign");
+        assert_eq!(Reading::Nato.spell('\\'), "backslash");
+        assert_eq!(Reading::Nato.spell('é'), "é U+00E9");
+        assert_eq!(Reading::Kana.spell('q'), "小文字のキュー");
+        assert_eq!(Reading::Kana.spell('W'), "大文字のダブリュー");
+        assert_eq!(Reading::Kana.spell('4'), "よん");
+        assert_eq!(Reading::Kana.spell('@'), "アットマーク");
+        assert_eq!(Reading::Kana.spell('あ'), "ひらがなのあ");
+        assert_eq!(Reading::Kana.spell('ア'), "カタカナのア");
+        for c in (' '..='~').filter(|c| !c.is_ascii_alphanumeric()) {
+            for reading in Reading::ALL.iter() {
+                assert!(reading.mark_name(c).is_some(), "{:?} has no name", c);
+            }
+        }
+        assert_eq!(Reading::Kana.mark_name('a'), None);
+    }
+
+    #[test]
+    fn chunks_test() {
+        let chunks = Reading::Nato.chunks("aB3^xyz@1", PHONETIC_DEFAULT_CHUNK);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].words, ["alfa", "capital Bravo", "three", "caret"]);
+        assert_eq!(chunks[0].to_string(), "1-4 aB3^: alfa, capital Bravo, three, caret");
+        assert_eq!((chunks[1].position, chunks[1].end()), (5, 8));
+        assert_eq!(chunks[2].to_string(), "9-9 1: one");
+        assert!(Reading::Nato.chunks("", PHONETIC_DEFAULT_CHUNK).is_empty());
+        assert_eq!(Reading::Kana.chunks("パス", 0).len(), 2);
+        assert_eq!(Reading::Nato.spell_all("a1"), ["alfa", "one"]);
+    }
+
+    #[test]
+    fn reading_name_test() {
+        for reading in Reading::ALL.iter() {
+            assert_eq!(reading.name().parse::<Reading>().unwrap(), *reading);
+        }
+        assert_eq!("NATO".parse::<Reading>().unwrap(), Reading::Nato);
+        assert!("morse".parse::<Reading>().is_err());
+    }
+}
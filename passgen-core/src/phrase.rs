@@ -0,0 +1,152 @@
+use alloc::{string::String, vec::Vec};
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{
+    password::{fill_volatile, Password},
+    PhraseError, PASSWORD_FILL_CHARACTER,
+};
+
+/// EFF large wordlist of 7776 words, one word per line, `#` starts comment line
+pub const PHRASE_WORDLIST: &str = include_str!("words/eff_large.txt");
+
+/// default passphrase has 6 words, that is about 77.5 bits
+pub const PHRASE_DEFAULT_WORDS: usize = 6;
+
+/// passphrase of fewer words than this is too weak, 4 words is about 51.7 bits
+pub const PHRASE_MIN_WORDS: usize = 4;
+
+/// max words of passphrase, which fits in password buffer
+pub const PHRASE_MAX_WORDS: usize = 64;
+
+/// default separator of words
+pub const PHRASE_DEFAULT_SEPARATOR: &str = "-";
+
+/// return words of the wordlist
+pub fn phrase_words() -> Vec<&'static str> {
+    PHRASE_WORDLIST.lines().filter(|line| !line.is_empty() && !line.starts_with('#')).collect()
+}
+
+/// generator of passphrase such as `cradle-unmade-zigzag-spout-evoke-juror`,
+/// whose words are drawn uniformly from the EFF large wordlist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhraseGenerator {
+    pub words: usize,
+    pub separator: String,
+    /// capitalize the first letter of each word
+    pub capitalize: bool,
+}
+
+impl Default for PhraseGenerator {
+    fn default() -> Self {
+        Self {
+            words: PHRASE_DEFAULT_WORDS,
+            separator: PHRASE_DEFAULT_SEPARATOR.into(),
+            capitalize: false,
+        }
+    }
+}
+
+impl PhraseGenerator {
+    /// return Ok if passphrase can be generated, or its error
+    pub fn can_generate(&self) -> anyhow::Result<()> {
+        if self.words < PHRASE_MIN_WORDS {
+            Err(PhraseError::TooFewWords(self.words))?
+        } else if self.words > PHRASE_MAX_WORDS {
+            Err(PhraseError::TooManyWords(self.words))?
+        } else if self.separator.chars().count() > 1 {
+            Err(PhraseError::LongSeparator(self.separator.clone()))?
+        }
+        Ok(())
+    }
+
+    /// return entropy bits of passphrase, which does not depend on separator or capitalization
+    pub fn entropy(&self) -> f64 {
+        self.words as f64 * libm::log2(phrase_words().len() as f64)
+    }
+
+    /// generate passphrase with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate_phrase(&self) -> anyhow::Result<Password> {
+        self.generate_phrase_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate passphrase with given CSPRNG
+    pub fn generate_phrase_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        self.can_generate()?;
+        let words = phrase_words();
+        let mut chars = Vec::new();
+        for i in 0..self.words {
+            if i > 0 {
+                chars.extend(self.separator.chars());
+            }
+            let word = words[csp_rng.gen_range(0, words.len())]; // rand 0.7
+            for (j, c) in word.chars().enumerate() {
+                chars.push(if self.capitalize && j == 0 { c.to_ascii_uppercase() } else { c });
+            }
+        }
+        let phrase = Password::from_chars(&chars);
+        fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+        phrase
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn wordlist_test() {
+        let words = phrase_words();
+        assert_eq!(words.len(), 7776);
+        assert_eq!((words[0], words[7775]), ("abacus", "zoom"));
+        assert!(words.iter().all(|word| word.chars().all(|c| c.is_ascii_lowercase() || c == '-')));
+        assert!((PhraseGenerator::default().entropy() - 6. * 7776f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn phrase_test() {
+        let words = phrase_words();
+        let generator = PhraseGenerator::default();
+        let phrase = generator.generate_phrase_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        // some words contain hyphen such as "t-shirt", so count them with the wordlist
+        let phrase = phrase.to_string();
+        let mut rest = &phrase[..];
+        for i in 0..PHRASE_DEFAULT_WORDS {
+            let word = words
+                .iter()
+                .filter(|word| rest.starts_with(*word))
+                .max_by_key(|word| word.len())
+                .unwrap();
+            rest = &rest[word.len()..];
+            if i + 1 < PHRASE_DEFAULT_WORDS {
+                rest = rest.strip_prefix('-').unwrap();
+            }
+        }
+        assert!(rest.is_empty());
+
+        let generator = PhraseGenerator { words: 5, separator: " ".into(), capitalize: true };
+        let phrase = generator.generate_phrase().unwrap().to_string();
+        let words: Vec<_> = phrase.split(' ').collect();
+        assert_eq!(words.len(), 5);
+        assert!(words.iter().all(|word| word.chars().next().unwrap().is_ascii_uppercase()));
+
+        let generator = PhraseGenerator { separator: String::new(), ..Default::default() };
+        assert!(generator.generate_phrase().unwrap().to_string().chars().all(|c| c != ' '));
+    }
+
+    #[test]
+    fn phrase_error_test() {
+        let generator = PhraseGenerator { words: 3, ..Default::default() };
+        let error = generator.generate_phrase().unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(PhraseError::TooFewWords(3))));
+        let generator = PhraseGenerator { words: 65, ..Default::default() };
+        assert!(generator.can_generate().is_err());
+        let generator = PhraseGenerator { separator: "--".into(), ..Default::default() };
+        assert!(generator.can_generate().is_err());
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use rand::{CryptoRng, Rng, RngCore};
+
+use super::{
+    password::{fill_volatile, Password},
+    PinError, PASSWORD_FILL_CHARACTER,
+};
+
+/// default PIN has 6 digits
+pub const PIN_DEFAULT_LENGTH: usize = 6;
+
+/// PIN shorter than 4 digits is too easy to guess
+pub const PIN_MIN_LENGTH: usize = 4;
+
+/// max length of PIN, for convenience
+pub const PIN_MAX_LENGTH: usize = 32;
+
+/// return true if PIN is a well known pattern, such as `000000`, `123456`, `987654` or `121212`
+pub fn is_weak_pin(pin: &str) -> bool {
+    let digits: Vec<i32> = pin.chars().filter_map(|c| c.to_digit(10)).map(|d| d as i32).collect();
+    if digits.len() < 2 {
+        return false;
+    }
+    let step = digits[1] - digits[0];
+    let sequence = matches!(step, -1..=1) && digits.windows(2).all(|w| w[1] - w[0] == step);
+    // repetition of 2 or 3 digits, such as 1212 or 123123
+    let repeated = (2..=3).any(|period| {
+        digits.len() >= 2 * period
+            && (period..digits.len()).all(|i| digits[i] == digits[i - period])
+    });
+    sequence || repeated
+}
+
+/// generator of numeric PIN, which rejects well known patterns by default
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PinGenerator {
+    pub len: usize,
+    /// allow well known patterns such as `123456`, which is rejected by `is_weak_pin` otherwise
+    pub allow_weak: bool,
+}
+
+impl Default for PinGenerator {
+    fn default() -> Self {
+        Self { len: PIN_DEFAULT_LENGTH, allow_weak: false }
+    }
+}
+
+impl PinGenerator {
+    /// return Ok if PIN can be generated, or its error
+    pub fn can_generate(&self) -> anyhow::Result<()> {
+        if self.len < PIN_MIN_LENGTH {
+            Err(PinError::TooShortLength(self.len))?
+        } else if self.len > PIN_MAX_LENGTH {
+            Err(PinError::TooLongLength(self.len))?
+        }
+        Ok(())
+    }
+
+    /// return entropy bits of PIN, rejected weak patterns are ignored
+    pub fn entropy(&self) -> f64 {
+        self.len as f64 * libm::log2(10.)
+    }
+
+    /// generate PIN with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate_pin(&self) -> anyhow::Result<Password> {
+        self.generate_pin_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate PIN with given CSPRNG, whose digits are drawn uniformly until it is not weak
+    pub fn generate_pin_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        self.can_generate()?;
+        loop {
+            let mut digits: Vec<char> = (0..self.len)
+                .map(|_| core::char::from_digit(csp_rng.gen_range(0, 10), 10).unwrap_or('0')) // rand 0.7
+                .collect();
+            let pin = Password::from_chars(&digits)?;
+            fill_volatile(&mut digits, PASSWORD_FILL_CHARACTER);
+            if self.allow_weak || !is_weak_pin(&pin.to_string()) {
+                return Ok(pin);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    #[test]
+    fn weak_pin_test() {
+        for weak in
+            ["0000", "111111", "1234", "123456", "987654", "3210", "1212", "123123", "909090"]
+                .iter()
+        {
+            assert!(is_weak_pin(weak), "{}", weak);
+        }
+        for strong in ["1235", "135790", "2468", "1221", "901234", "482915"].iter() {
+            assert!(!is_weak_pin(strong), "{}", strong);
+        }
+    }
+
+    #[test]
+    fn pin_test() {
+        let generator = PinGenerator::default();
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..1000 {
+            let pin = generator.generate_pin_with_rng(&mut rng).unwrap().to_string();
+            assert_eq!(pin.len(), PIN_DEFAULT_LENGTH);
+            assert!(pin.chars().all(|c| c.is_ascii_digit()));
+            assert!(!is_weak_pin(&pin));
+        }
+        let generator = PinGenerator { len: 4, allow_weak: true };
+        assert!((generator.entropy() - 4. * 10f64.log2()).abs() < 1e-9);
+        assert_eq!(generator.generate_pin_with_rng(&mut rng).unwrap().to_string().len(), 4);
+    }
+
+    #[test]
+    fn pin_error_test() {
+        let error = PinGenerator { len: 3, allow_weak: false }.generate_pin().unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(PinError::TooShortLength(3))));
+        assert!(PinGenerator { len: 33, allow_weak: false }.can_generate().is_err());
+    }
+}
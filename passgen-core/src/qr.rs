@@ -0,0 +1,291 @@
+use core::str::FromStr;
+use qrcode::{Color, EcLevel, QrCode};
+
+use super::{
+    password::{fill_volatile, zero_string, Password},
+    ParseError, WifiError, PASSWORD_FILL_CHARACTER,
+};
+
+/// light modules around QR code, 4 modules is required by the specification
+pub const QR_QUIET_ZONE: usize = 4;
+
+/// WPA passphrase is 8 to 63 printable ASCII characters (IEEE 802.11i)
+pub const WIFI_WPA_PASSPHRASE_LENGTH: core::ops::RangeInclusive<usize> = 8..=63;
+
+/// QR code of secret, such as password or Wi-Fi credential.
+/// modules are cleared at dropped time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Qr {
+    width: usize,
+    modules: Vec<bool>,
+}
+
+impl Drop for Qr {
+    fn drop(&mut self) {
+        fill_volatile(&mut self.modules, false);
+    }
+}
+
+impl Qr {
+    /// encode data into QR code with error correction level M
+    pub fn new(data: &str) -> anyhow::Result<Self> {
+        let code = QrCode::with_error_correction_level(data, EcLevel::M)?;
+        let width = code.width();
+        let modules = code.into_colors().into_iter().map(|color| color == Color::Dark).collect();
+        Ok(Self { width, modules })
+    }
+
+    /// return the number of modules of each side, without quiet zone
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// return true if the module is dark, modules out of code (quiet zone) are light
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.width && self.modules[y * self.width + x]
+    }
+
+    /// render with unicode half blocks, each character is two modules of upper and lower.
+    /// light modules are drawn as blocks as `qrencode -t UTF8`, for terminals of dark background
+    pub fn to_half_blocks(&self) -> String {
+        let size = self.width + 2 * QR_QUIET_ZONE;
+        let is_light = |x: usize, y: usize| {
+            y < size && !self.is_dark(x.wrapping_sub(QR_QUIET_ZONE), y.wrapping_sub(QR_QUIET_ZONE))
+        };
+        let mut blocks = String::new();
+        for y in (0..size).step_by(2) {
+            for x in 0..size {
+                blocks.push(match (is_light(x, y), is_light(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            blocks.push('\n');
+        }
+        blocks
+    }
+
+    /// return path data of dark modules, whose coordinate includes quiet zone
+    pub fn to_svg_path(&self) -> String {
+        let mut path = String::new();
+        for y in 0..self.width {
+            for x in 0..self.width {
+                if self.is_dark(x, y) {
+                    path += &format!("M{},{}h1v1h-1z", x + QR_QUIET_ZONE, y + QR_QUIET_ZONE);
+                }
+            }
+        }
+        path
+    }
+
+    /// return the number of modules of each side of svg, including quiet zone
+    pub fn svg_size(&self) -> usize {
+        self.width + 2 * QR_QUIET_ZONE
+    }
+
+    /// render as svg document, which is scaled by its container
+    pub fn to_svg(&self) -> String {
+        let size = self.svg_size();
+        format!(
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {0}" shape-rendering="crispEdges">"#,
+                r##"<rect width="{0}" height="{0}" fill="#ffffff"/>"##,
+                r##"<path d="{1}" fill="#000000"/>"##,
+                "</svg>",
+            ),
+            size,
+            self.to_svg_path()
+        )
+    }
+}
+
+/// authentication type of Wi-Fi network
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum WifiSecurity {
+    /// WPA, WPA2 or WPA3 personal
+    #[default]
+    Wpa,
+    Wep,
+    /// open network, password is not used
+    Nopass,
+}
+
+impl FromStr for WifiSecurity {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|security| security.name().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| ParseError::UnknownWifiSecurity(s.to_string()))
+    }
+}
+
+impl WifiSecurity {
+    pub const ALL: [WifiSecurity; 3] = [WifiSecurity::Wpa, WifiSecurity::Wep, WifiSecurity::Nopass];
+
+    /// return name of this security in `WIFI:T:` field, such as "WPA"
+    pub fn name(&self) -> &'static str {
+        match self {
+            WifiSecurity::Wpa => "WPA",
+            WifiSecurity::Wep => "WEP",
+            WifiSecurity::Nopass => "nopass",
+        }
+    }
+}
+
+/// Wi-Fi network, whose credential can be joined by scanning QR code on phones
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WifiNetwork {
+    pub ssid: String,
+    pub security: WifiSecurity,
+    pub hidden: bool,
+}
+
+impl WifiNetwork {
+    pub fn new(ssid: &str) -> Self {
+        Self { ssid: ssid.to_string(), ..Default::default() }
+    }
+
+    /// escape `\`, `;`, `,`, `:` and `"` with backslash, as `WIFI:` format of ZXing
+    pub fn escape(s: &str) -> String {
+        let mut escaped = String::with_capacity(s.len());
+        for c in s.chars() {
+            if matches!(c, '\\' | ';' | ',' | ':' | '"') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped
+    }
+
+    /// return Ok if password can be used for this network, or its error
+    pub fn validate(&self, password: &str) -> anyhow::Result<()> {
+        if self.ssid.is_empty() {
+            Err(WifiError::EmptySsid)?
+        }
+        if self.security == WifiSecurity::Wpa {
+            let len = password.chars().count();
+            if !WIFI_WPA_PASSPHRASE_LENGTH.contains(&len) {
+                Err(WifiError::InvalidPassphraseLength(len))?
+            }
+            if let Some(c) = password.chars().find(|c| !(' '..='~').contains(c)) {
+                Err(WifiError::InvalidPassphraseChar(c))?
+            }
+        }
+        Ok(())
+    }
+
+    /// return `WIFI:T:WPA;S:<ssid>;P:<password>;;` string to be encoded into QR code
+    pub fn payload(&self, password: &str) -> anyhow::Result<Password> {
+        self.validate(password)?;
+        let mut payload =
+            format!("WIFI:T:{};S:{};", self.security.name(), Self::escape(&self.ssid));
+        if self.security != WifiSecurity::Nopass {
+            payload += &format!("P:{};", Self::escape(password));
+        }
+        if self.hidden {
+            payload += "H:true;";
+        }
+        payload.push(';');
+        let mut chars: Vec<char> = payload.chars().collect();
+        let secret = Password::from_chars(&chars);
+        fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+        zero_string(&mut payload);
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn qr_test() {
+        // "hello" fits in version 1 of 21x21 modules
+        let qr = Qr::new("hello").unwrap();
+        assert_eq!(qr.width(), 21);
+        // finder pattern at top left corner
+        assert!(qr.is_dark(0, 0) && qr.is_dark(6, 6) && qr.is_dark(3, 3));
+        assert!(!qr.is_dark(1, 1) && !qr.is_dark(7, 7));
+        assert!(!qr.is_dark(21, 0) && !qr.is_dark(0, 21));
+
+        let long = "a".repeat(128);
+        assert!(Qr::new(&long).unwrap().width() > 21);
+        assert!(Qr::new(&"a".repeat(8192)).is_err());
+    }
+
+    #[test]
+    fn half_blocks_test() {
+        let qr = Qr::new("hello").unwrap();
+        let blocks = qr.to_half_blocks();
+        let lines: Vec<_> = blocks.lines().collect();
+        // 29 rows with quiet zone, rendered into 15 lines
+        assert_eq!(lines.len(), 15);
+        assert!(lines.iter().all(|line| line.chars().count() == 29));
+        // first line is quiet zone, last line has light upper half only
+        assert!(lines[0].chars().all(|c| c == '█'));
+        assert!(lines[14].chars().all(|c| c == '▀'));
+        // top of finder pattern is dark in line of y=4 and y=5
+        assert_eq!(lines[2].chars().nth(4), Some(' '));
+    }
+
+    #[test]
+    fn svg_test() {
+        let qr = Qr::new("hello").unwrap();
+        let svg = qr.to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 29 29\""));
+        assert!(svg.ends_with("</svg>"));
+        assert!(qr.to_svg_path().starts_with("M4,4h1v1h-1z"));
+        let dark =
+            (0..21).flat_map(|y| (0..21).map(move |x| (x, y))).filter(|&(x, y)| qr.is_dark(x, y));
+        assert_eq!(qr.to_svg_path().matches('M').count(), dark.count());
+    }
+
+    #[test]
+    fn wifi_escape_test() {
+        assert_eq!(WifiNetwork::escape("plain"), "plain");
+        assert_eq!(WifiNetwork::escape(r#"a;b,c:d\e"f"#), r#"a\;b\,c\:d\\e\"f"#);
+    }
+
+    #[test]
+    fn wifi_payload_test() {
+        let network = WifiNetwork::new("my;net");
+        let payload = network.payload("pass:word,1").unwrap();
+        assert_eq!(payload.to_string(), r"WIFI:T:WPA;S:my\;net;P:pass\:word\,1;;");
+
+        let network =
+            WifiNetwork { ssid: "cafe".into(), security: WifiSecurity::Nopass, hidden: true };
+        assert_eq!(
+            network.payload("ignored").unwrap().to_string(),
+            "WIFI:T:nopass;S:cafe;H:true;;"
+        );
+
+        let network =
+            WifiNetwork { ssid: "old".into(), security: WifiSecurity::Wep, hidden: false };
+        assert_eq!(network.payload("12345").unwrap().to_string(), "WIFI:T:WEP;S:old;P:12345;;");
+    }
+
+    #[test]
+    fn wifi_invalid_test() {
+        let error = WifiNetwork::new("").payload("password").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(WifiError::EmptySsid)));
+        let error = WifiNetwork::new("net").payload("short").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(WifiError::InvalidPassphraseLength(5))));
+        let error = WifiNetwork::new("net").payload(&"a".repeat(64)).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(WifiError::InvalidPassphraseLength(64))));
+        let error = WifiNetwork::new("net").payload("passwordπ").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(WifiError::InvalidPassphraseChar('π'))));
+    }
+
+    #[test]
+    fn parse_wifi_security_test() {
+        for security in WifiSecurity::ALL.iter() {
+            assert_eq!(security.name().parse::<WifiSecurity>().unwrap(), *security);
+        }
+        assert_eq!("wpa".parse::<WifiSecurity>().unwrap(), WifiSecurity::Wpa);
+        assert!("wpa3".parse::<WifiSecurity>().is_err());
+    }
+}
@@ -0,0 +1,48 @@
+/// lower case alphabet
+pub const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+pub const LOWER_SET: [char; 26] = ascii_chars(LOWER);
+
+/// upper case alphabet
+pub const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+pub const UPPER_SET: [char; 26] = ascii_chars(UPPER);
+
+/// numeric
+pub const NUMERIC: &str = "0123456789";
+pub const NUMERIC_SET: [char; 10] = ascii_chars(NUMERIC);
+
+/// convert ascii string into char array at compile time, without allocation
+pub(crate) const fn ascii_chars<const N: usize>(s: &str) -> [char; N] {
+    let bytes = s.as_bytes();
+    assert!(bytes.len() == N, "length of array should be equal to length of string");
+    let mut chars = ['\0'; N];
+    let mut i = 0;
+    while i < N {
+        assert!(bytes[i].is_ascii(), "string should be ascii");
+        chars[i] = bytes[i] as char;
+        i += 1;
+    }
+    chars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lower_test() {
+        assert_eq!(LOWER, &('a'..='z').collect::<String>());
+    }
+
+    #[test]
+    fn upper_test() {
+        assert_eq!(UPPER, &('A'..='Z').collect::<String>());
+    }
+
+    #[test]
+    fn numeric_test() {
+        assert_eq!(
+            NUMERIC,
+            &(0..=9).map(|i| std::char::from_digit(i, 10).unwrap()).collect::<String>(),
+        )
+    }
+}
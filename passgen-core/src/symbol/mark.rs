@@ -0,0 +1,272 @@
+use alloc::{
+    collections::{btree_set, BTreeSet},
+    string::ToString,
+};
+use core::str::FromStr;
+
+use super::{super::ParseError, alphanumeric::ascii_chars, unicode};
+
+/// preset of marks, which can be used without quoting in some context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkPreset {
+    ShellSafe,
+    UrlSafe,
+    YamlSafe,
+    XmlSafe,
+}
+
+impl FromStr for MarkPreset {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase();
+        Self::ALL
+            .iter()
+            .find(|preset| preset.name() == name.trim_end_matches("-safe"))
+            .copied()
+            .ok_or_else(|| ParseError::UnknownPreset(s.to_string()))
+    }
+}
+
+impl MarkPreset {
+    pub const ALL: [MarkPreset; 4] =
+        [MarkPreset::ShellSafe, MarkPreset::UrlSafe, MarkPreset::YamlSafe, MarkPreset::XmlSafe];
+
+    /// return name of this preset, such as "shell"
+    pub fn name(&self) -> &'static str {
+        match self {
+            MarkPreset::ShellSafe => "shell",
+            MarkPreset::UrlSafe => "url",
+            MarkPreset::YamlSafe => "yaml",
+            MarkPreset::XmlSafe => "xml",
+        }
+    }
+
+    /// return candidate marks which are safe in this preset's context
+    pub fn safe_marks(&self) -> &'static str {
+        match self {
+            MarkPreset::ShellSafe => ".,_-+=/@%:", // no glob, quote, expansion and redirect
+            MarkPreset::UrlSafe => "._-",          // unreserved characters of RFC 3986
+            MarkPreset::YamlSafe => "._-+=/^$;()", // no indicator of plain scalar
+            MarkPreset::XmlSafe => ".,_-+=/\\^!?@#$%:;()[]{}", // no entity is needed
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MarkSet {
+    mark: BTreeSet<char>,
+}
+impl Default for MarkSet {
+    fn default() -> Self {
+        Self { mark: Self::DEFAULT_MARK.chars().collect() }
+    }
+}
+#[cfg(feature = "serde")]
+impl serde::Serialize for MarkSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.mark.iter().collect::<alloc::string::String>())
+    }
+}
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MarkSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let marks = <alloc::string::String as serde::Deserialize>::deserialize(deserializer)?;
+        let mut set = Self::new();
+        for mark in marks.chars() {
+            if !set.insert(mark) && !set.contains(&mark) {
+                let message = alloc::format!("{:?} cannot be used as mark", mark);
+                return Err(serde::de::Error::custom(message));
+            }
+        }
+        Ok(set)
+    }
+}
+impl MarkSet {
+    pub const DEFAULT_MARK: &str = "^!@#&";
+    pub const DEFAULT_MARK_SET: [char; 5] = ascii_chars(Self::DEFAULT_MARK);
+    pub const CANDIDATE_MARK: &str = ".,_-+=/\\^!?@#&\"'$%:;><()[]{}";
+    pub const CANDIDATE_MARK_SET: [char; 28] = ascii_chars(Self::CANDIDATE_MARK);
+
+    /// return new empty MarkSet
+    pub fn new() -> Self {
+        Self { mark: BTreeSet::new() }
+    }
+
+    /// return new MarkSet, which contains all safe marks of given preset
+    pub fn from_preset(preset: MarkPreset) -> Self {
+        Self { mark: preset.safe_marks().chars().collect() }
+    }
+
+    /// remove marks which are not safe in given preset
+    pub fn restrict(&mut self, preset: MarkPreset) {
+        let safe = preset.safe_marks();
+        self.mark.retain(|&c| safe.contains(c))
+    }
+
+    /// return true if this set contains the value
+    pub fn contains(&self, mark: &char) -> bool {
+        self.mark.contains(mark)
+    }
+
+    /// return true if this set contains nothing
+    pub fn is_empty(&self) -> bool {
+        self.mark.is_empty()
+    }
+
+    /// return iterator candidate mark and is it contains this set, and other inserted marks
+    pub fn get_marks(&self) -> impl Iterator<Item = (char, bool)> + '_ {
+        let others = self.mark.iter().filter(|c| !Self::CANDIDATE_MARK.contains(**c));
+        Self::CANDIDATE_MARK
+            .chars()
+            .map(move |c| (c, self.contains(&c)))
+            .chain(others.map(|&c| (c, true)))
+    }
+
+    /// get iterator of this set
+    pub fn iter(&self) -> btree_set::Iter<'_, char> {
+        self.mark.iter()
+    }
+
+    /// if mark is contained remove it, not contained and contained in candidate insert it
+    pub fn toggle(&mut self, mark: char) -> bool {
+        match self.contains(&mark) {
+            true => self.remove(&mark),
+            false => self.insert(mark),
+        }
+    }
+
+    /// if mark is contained in candidate mark, or is unicode punctuation or symbol, insert it
+    pub fn insert(&mut self, mark: char) -> bool {
+        if Self::CANDIDATE_MARK_SET.contains(&mark) || unicode::is_mark_char(mark) {
+            self.mark.insert(mark)
+        } else {
+            false
+        }
+    }
+
+    /// all mark contains in this set
+    pub fn insert_all(&mut self) {
+        self.mark.extend(Self::CANDIDATE_MARK_SET.iter())
+    }
+
+    /// if mark is contained, remove it
+    pub fn remove(&mut self, mark: &char) -> bool {
+        self.mark.remove(mark)
+    }
+
+    /// clear contains elements
+    pub fn clear(&mut self) {
+        self.mark.clear()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn all_mark_is_different_test() {
+        assert_eq!(
+            MarkSet::DEFAULT_MARK.chars().collect::<Vec<_>>().len(),
+            MarkSet::DEFAULT_MARK.chars().collect::<HashSet<_>>().len()
+        );
+        assert_eq!(
+            MarkSet::CANDIDATE_MARK.chars().collect::<HashSet<_>>().len(),
+            MarkSet::CANDIDATE_MARK_SET.len(),
+        )
+    }
+
+    #[test]
+    fn candidate_mark_contain_all_default_mark_test() {
+        let mut default = HashSet::new();
+        for c in MarkSet::CANDIDATE_MARK.chars() {
+            if MarkSet::DEFAULT_MARK_SET.contains(&c) {
+                default.insert(c);
+            }
+        }
+        assert_eq!(default, MarkSet::DEFAULT_MARK_SET.iter().copied().collect());
+    }
+
+    #[test]
+    fn contains_test() {
+        let default = MarkSet::default();
+        assert!(default.contains(&'!'));
+        assert!(default.contains(&'#'));
+        assert!(!default.contains(&'a'));
+        assert!(!default.contains(&'A'));
+        assert!(!default.contains(&'0'));
+        assert!(!default.contains(&'\\'));
+    }
+
+    #[test]
+    fn remove_insert_test() {
+        let mut ms = MarkSet::default();
+        {
+            assert!(!ms.insert('a')); // 'a' is not contains in candidate mark, so returned false
+            assert!(!ms.contains(&'a'));
+        }
+        {
+            assert!(!ms.contains(&'?'));
+            assert!(!ms.remove(&'?'));
+            assert!(!ms.contains(&'?'));
+            assert!(ms.insert('?'));
+            assert!(ms.contains(&'?'));
+            assert!(ms.remove(&'?'));
+            assert!(!ms.contains(&'?'));
+        }
+    }
+
+    #[test]
+    fn toggle_test() {
+        let mut ms = MarkSet::default();
+        assert!(!ms.contains(&'/'));
+        assert!(ms.toggle('/'));
+        assert!(ms.contains(&'/'));
+        assert!(ms.toggle('/'));
+        assert!(!ms.contains(&'/'));
+    }
+
+    #[test]
+    fn preset_test() {
+        for preset in MarkPreset::ALL.iter() {
+            assert!(preset.safe_marks().chars().all(|c| MarkSet::CANDIDATE_MARK_SET.contains(&c)));
+            assert_eq!(preset.name().parse::<MarkPreset>().unwrap(), *preset);
+        }
+        assert_eq!("Shell-Safe".parse::<MarkPreset>().unwrap(), MarkPreset::ShellSafe);
+        assert!("json".parse::<MarkPreset>().is_err());
+        assert!(!MarkPreset::XmlSafe.safe_marks().contains(|c| "<>&\"'".contains(c)));
+    }
+
+    #[test]
+    fn restrict_test() {
+        let mut ms = MarkSet::default();
+        ms.restrict(MarkPreset::ShellSafe);
+        assert_eq!(ms.iter().collect::<Vec<_>>(), vec![&'@']);
+        ms.restrict(MarkPreset::UrlSafe);
+        assert!(ms.is_empty());
+        let ms = MarkSet::from_preset(MarkPreset::UrlSafe);
+        assert!(ms.contains(&'.') && ms.contains(&'_') && ms.contains(&'-'));
+    }
+
+    #[test]
+    fn insert_unicode_mark_test() {
+        let mut ms = MarkSet::default();
+        assert!(ms.insert('€'));
+        assert!(ms.insert('«'));
+        assert!(!ms.insert('é'));
+        assert!(!ms.insert('\u{0301}'));
+        let marks: Vec<_> = ms.get_marks().collect();
+        assert_eq!(marks.len(), MarkSet::CANDIDATE_MARK.chars().count() + 2);
+        assert_eq!(marks[marks.len() - 2..], [('«', true), ('€', true)]);
+    }
+
+    #[test]
+    fn toggle_return_false_test() {
+        let mut ms = MarkSet::default();
+        assert!(!ms.toggle('a'));
+        assert!(!ms.toggle('A'));
+        assert!(!ms.toggle('0'));
+    }
+}
@@ -1,5 +1,7 @@
 mod alphanumeric;
 mod mark;
+mod unicode;
 
 pub use alphanumeric::*;
 pub use mark::*;
+pub use unicode::*;
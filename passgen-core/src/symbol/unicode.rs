@@ -0,0 +1,182 @@
+use alloc::{collections::BTreeSet, string::ToString};
+use core::str::FromStr;
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::UnicodeNormalization;
+
+use super::super::ParseError;
+
+/// optional class of non-ascii characters, each character is a single code point grapheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UnicodeClass {
+    Latin1,
+    Greek,
+    Cyrillic,
+    Kana,
+    Emoji,
+}
+
+impl FromStr for UnicodeClass {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|class| class.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownUnicodeClass(s.to_string()))
+    }
+}
+
+impl UnicodeClass {
+    pub const ALL: [UnicodeClass; 5] = [
+        UnicodeClass::Latin1,
+        UnicodeClass::Greek,
+        UnicodeClass::Cyrillic,
+        UnicodeClass::Kana,
+        UnicodeClass::Emoji,
+    ];
+
+    /// return name of this class, such as "greek"
+    pub fn name(&self) -> &'static str {
+        match self {
+            UnicodeClass::Latin1 => "latin1",
+            UnicodeClass::Greek => "greek",
+            UnicodeClass::Cyrillic => "cyrillic",
+            UnicodeClass::Kana => "kana",
+            UnicodeClass::Emoji => "emoji",
+        }
+    }
+
+    /// return inclusive code point ranges of this class
+    fn ranges(&self) -> &'static [(char, char)] {
+        match self {
+            // letters of Latin-1 Supplement, except for multiplication and division sign
+            UnicodeClass::Latin1 => &[('À', 'Ö'), ('Ø', 'ö'), ('ø', 'ÿ')],
+            UnicodeClass::Greek => &[('Α', 'Ρ'), ('Σ', 'Ω'), ('α', 'ω')],
+            UnicodeClass::Cyrillic => &[('Ё', 'Ё'), ('А', 'я'), ('ё', 'ё')],
+            UnicodeClass::Kana => &[('ぁ', 'ゖ'), ('ァ', 'ヺ')],
+            // emoticons, which are displayed as emoji without variation selector
+            UnicodeClass::Emoji => &[('😀', '🙏')],
+        }
+    }
+
+    /// return characters of this class
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        self.ranges().iter().flat_map(|&(start, end)| start..=end)
+    }
+
+    /// return true if this class contains the character
+    pub fn contains(&self, c: &char) -> bool {
+        self.ranges().iter().any(|(start, end)| start <= c && c <= end)
+    }
+}
+
+/// return true if the character can be used in password, control, format, unassigned,
+/// private use, separator except for space, and combining mark are rejected
+pub fn is_valid_char(c: char) -> bool {
+    !matches!(
+        get_general_category(c),
+        GeneralCategory::Control
+            | GeneralCategory::Format
+            | GeneralCategory::Unassigned
+            | GeneralCategory::PrivateUse
+            | GeneralCategory::Surrogate
+            | GeneralCategory::LineSeparator
+            | GeneralCategory::ParagraphSeparator
+            | GeneralCategory::NonspacingMark
+            | GeneralCategory::SpacingMark
+            | GeneralCategory::EnclosingMark
+    )
+}
+
+/// return true if the character is punctuation or symbol, which can be used as mark
+pub fn is_mark_char(c: char) -> bool {
+    matches!(
+        get_general_category(c),
+        GeneralCategory::ConnectorPunctuation
+            | GeneralCategory::DashPunctuation
+            | GeneralCategory::OpenPunctuation
+            | GeneralCategory::ClosePunctuation
+            | GeneralCategory::InitialPunctuation
+            | GeneralCategory::FinalPunctuation
+            | GeneralCategory::OtherPunctuation
+            | GeneralCategory::MathSymbol
+            | GeneralCategory::CurrencySymbol
+            | GeneralCategory::ModifierSymbol
+            | GeneralCategory::OtherSymbol
+    )
+}
+
+/// NFC normalize input such as addition form, and collect its characters
+pub fn normalized_chars(input: &str) -> BTreeSet<char> {
+    input.nfc().collect()
+}
+
+/// serialize set of characters as a string, such as addition
+#[cfg(feature = "serde")]
+pub(crate) mod serde_chars {
+    use alloc::{collections::BTreeSet, string::String};
+
+    pub fn serialize<S: serde::Serializer>(
+        chars: &BTreeSet<char>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&chars.iter().collect::<String>())
+    }
+
+    pub fn deserialize<'de, D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeSet<char>, D::Error> {
+        let chars = <String as serde::Deserialize>::deserialize(deserializer)?;
+        Ok(super::normalized_chars(&chars))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_test() {
+        assert_eq!(UnicodeClass::Latin1.chars().count(), 62);
+        assert_eq!(UnicodeClass::Greek.chars().count(), 49);
+        assert_eq!(UnicodeClass::Cyrillic.chars().count(), 66);
+        assert!(UnicodeClass::Kana.contains(&'あ') && UnicodeClass::Kana.contains(&'ア'));
+        assert!(!UnicodeClass::Latin1.contains(&'×'));
+        assert!(UnicodeClass::Emoji.contains(&'😀'));
+        assert_eq!("Greek".parse::<UnicodeClass>().unwrap(), UnicodeClass::Greek);
+        assert!("hangul".parse::<UnicodeClass>().is_err());
+    }
+
+    #[test]
+    fn class_chars_are_valid_nfc_test() {
+        for class in UnicodeClass::ALL.iter() {
+            for c in class.chars() {
+                assert!(is_valid_char(c), "{:?}", c);
+                assert_eq!(c.to_string().nfc().collect::<String>(), c.to_string(), "{:?}", c);
+            }
+        }
+    }
+
+    #[test]
+    fn valid_char_test() {
+        assert!(is_valid_char('a') && is_valid_char('€') && is_valid_char(' '));
+        assert!(!is_valid_char('\n'));
+        assert!(!is_valid_char('\u{200d}')); // zero width joiner
+        assert!(!is_valid_char('\u{202e}')); // right-to-left override
+        assert!(!is_valid_char('\u{0301}')); // combining acute accent
+        assert!(!is_valid_char('\u{0378}')); // unassigned
+        assert!(!is_valid_char('\u{e000}')); // private use
+    }
+
+    #[test]
+    fn mark_char_test() {
+        assert!(is_mark_char('!') && is_mark_char('€') && is_mark_char('«'));
+        assert!(!is_mark_char('a') && !is_mark_char('0') && !is_mark_char('é'));
+    }
+
+    #[test]
+    fn normalized_chars_test() {
+        let chars = normalized_chars("e\u{0301}\u{212b}");
+        assert_eq!(chars, vec!['é', 'Å'].into_iter().collect());
+    }
+}
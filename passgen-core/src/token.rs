@@ -0,0 +1,393 @@
+use alloc::{string::ToString, vec, vec::Vec};
+use core::str::FromStr;
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    password::{fill_volatile, Password},
+    ParseError, TokenError, PASSWORD_FILL_CHARACTER,
+};
+
+/// default size of token is 32 bytes, that is 256 bits
+pub const TOKEN_DEFAULT_BYTES: usize = 32;
+
+/// max size of token is 512 bytes, whose hex fits in password buffer
+pub const TOKEN_MAX_BYTES: usize = 512;
+pub const TOKEN_MAX_BITS: usize = TOKEN_MAX_BYTES * 8;
+
+/// RFC 4648 base16 alphabet, in lower case
+pub const HEX_ALPHABET: &str = "0123456789abcdef";
+
+/// RFC 4648 base32 alphabet
+pub const BASE32_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// RFC 4648 base64 alphabet
+pub const BASE64_ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// RFC 4648 base64 alphabet with URL and filename safe characters
+pub const BASE64URL_ALPHABET: &str =
+    "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// base62 alphabet, which is alphanumeric
+pub const BASE62_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// bitcoin base58 alphabet, without `0`, `O`, `I` and `l`
+pub const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Crockford base32 alphabet, without `I`, `L`, `O` and `U`
+pub const CROCKFORD_ALPHABET: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// padding character of RFC 4648 base32 and base64
+pub const PADDING: char = '=';
+
+/// encoding of random token, such as API key or session secret
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Encoding {
+    Hex,
+    Base32,
+    Base64,
+    #[default]
+    Base64Url,
+    Base62,
+    Base58,
+    Crockford,
+}
+
+impl FromStr for Encoding {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .find(|encoding| encoding.name() == s.to_lowercase())
+            .copied()
+            .ok_or_else(|| ParseError::UnknownEncoding(s.to_string()))
+    }
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 7] = [
+        Encoding::Hex,
+        Encoding::Base32,
+        Encoding::Base64,
+        Encoding::Base64Url,
+        Encoding::Base62,
+        Encoding::Base58,
+        Encoding::Crockford,
+    ];
+
+    /// return name of this encoding, such as "base64url"
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Hex => "hex",
+            Encoding::Base32 => "base32",
+            Encoding::Base64 => "base64",
+            Encoding::Base64Url => "base64url",
+            Encoding::Base62 => "base62",
+            Encoding::Base58 => "base58",
+            Encoding::Crockford => "crockford",
+        }
+    }
+
+    /// return alphabet of this encoding
+    pub fn alphabet(&self) -> &'static str {
+        match self {
+            Encoding::Hex => HEX_ALPHABET,
+            Encoding::Base32 => BASE32_ALPHABET,
+            Encoding::Base64 => BASE64_ALPHABET,
+            Encoding::Base64Url => BASE64URL_ALPHABET,
+            Encoding::Base62 => BASE62_ALPHABET,
+            Encoding::Base58 => BASE58_ALPHABET,
+            Encoding::Crockford => CROCKFORD_ALPHABET,
+        }
+    }
+
+    /// return true if RFC 4648 defines padding for this encoding
+    pub fn has_padding(&self) -> bool {
+        matches!(self, Encoding::Base32 | Encoding::Base64 | Encoding::Base64Url)
+    }
+
+    /// return entropy bits of one character
+    pub fn bits_per_char(&self) -> f64 {
+        libm::log2(self.alphabet().len() as f64)
+    }
+
+    /// encode bytes, padding is ignored if this encoding has no padding
+    pub fn encode(&self, bytes: &[u8], padding: bool) -> Vec<char> {
+        let alphabet = self.alphabet().as_bytes();
+        match self {
+            Encoding::Base62 | Encoding::Base58 => encode_radix(bytes, alphabet),
+            _ => {
+                let bits = alphabet.len().trailing_zeros() as usize;
+                let mut encoded = encode_bits(bytes, alphabet, bits);
+                if padding && self.has_padding() {
+                    // base32 is padded to 8 characters, and base64 is padded to 4 characters
+                    let block = if bits == 5 { 8 } else { 4 };
+                    while !encoded.len().is_multiple_of(block) {
+                        encoded.push(PADDING);
+                    }
+                }
+                encoded
+            }
+        }
+    }
+}
+
+/// encode bytes by `bits` bits per character, from most significant bit
+fn encode_bits(bytes: &[u8], alphabet: &[u8], bits: usize) -> Vec<char> {
+    let mut encoded = Vec::with_capacity((bytes.len() * 8).div_ceil(bits));
+    let (mut buffer, mut buffered) = (0u32, 0);
+    for &byte in bytes {
+        buffer = (buffer << 8) | byte as u32;
+        buffered += 8;
+        while buffered >= bits {
+            buffered -= bits;
+            encoded.push(alphabet[(buffer >> buffered) as usize & ((1 << bits) - 1)] as char);
+        }
+    }
+    if buffered > 0 {
+        encoded.push(alphabet[(buffer << (bits - buffered)) as usize & ((1 << bits) - 1)] as char);
+    }
+    encoded
+}
+
+/// encode bytes as big number in radix of alphabet length, such as base58.
+/// each leading zero byte is encoded as first character, as bitcoin base58
+fn encode_radix(bytes: &[u8], alphabet: &[u8]) -> Vec<char> {
+    let radix = alphabet.len();
+    let zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+    // little endian digits in the radix
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 2);
+    for &byte in &bytes[zeros..] {
+        let mut carry = byte as usize;
+        for digit in digits.iter_mut() {
+            carry += (*digit as usize) << 8;
+            *digit = (carry % radix) as u8;
+            carry /= radix;
+        }
+        while carry > 0 {
+            digits.push((carry % radix) as u8);
+            carry /= radix;
+        }
+    }
+    let mut encoded = vec![alphabet[0] as char; zeros];
+    encoded.extend(digits.iter().rev().map(|&digit| alphabet[digit as usize] as char));
+    fill_volatile(&mut digits, 0);
+    encoded
+}
+
+/// size of token, in random bytes or in entropy bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenSize {
+    Bytes(usize),
+    Bits(usize),
+}
+
+impl Default for TokenSize {
+    fn default() -> Self {
+        TokenSize::Bytes(TOKEN_DEFAULT_BYTES)
+    }
+}
+
+/// generator of random token, which is encoded random bytes rather than human password
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TokenGenerator {
+    pub size: TokenSize,
+    pub encoding: Encoding,
+    pub padding: bool,
+}
+
+impl TokenGenerator {
+    pub fn new(size: TokenSize, encoding: Encoding, padding: bool) -> Self {
+        Self { size, encoding, padding }
+    }
+
+    /// return Ok if token can be generated, or its error
+    pub fn can_generate(&self) -> anyhow::Result<()> {
+        match self.size {
+            TokenSize::Bytes(0) | TokenSize::Bits(0) => Err(TokenError::EmptySize)?,
+            TokenSize::Bytes(bytes) if bytes > TOKEN_MAX_BYTES => {
+                Err(TokenError::TooManyBytes(bytes))?
+            }
+            TokenSize::Bits(bits) if bits > TOKEN_MAX_BITS => Err(TokenError::TooManyBits(bits))?,
+            _ => Ok(()),
+        }
+    }
+
+    /// return the number of characters of token, which is max one for base58 of random bytes
+    pub fn get_len(&self) -> usize {
+        match self.size {
+            TokenSize::Bytes(bytes) => self.encoding.encode(&vec![0xff; bytes], self.padding).len(),
+            TokenSize::Bits(bits) => {
+                libm::ceil(bits as f64 / self.encoding.bits_per_char()) as usize
+            }
+        }
+    }
+
+    /// return entropy bits of token
+    pub fn entropy(&self) -> f64 {
+        match self.size {
+            TokenSize::Bytes(bytes) => (bytes * 8) as f64,
+            TokenSize::Bits(_) => self.get_len() as f64 * self.encoding.bits_per_char(),
+        }
+    }
+
+    /// generate token with CSPRNG
+    #[cfg(feature = "std")]
+    pub fn generate_token(&self) -> anyhow::Result<Password> {
+        self.generate_token_with_rng(&mut rand::thread_rng())
+    }
+
+    /// generate token with given CSPRNG. in bytes, random bytes are encoded.
+    /// in bits, each character is drawn uniformly, so the token has at least given bits
+    pub fn generate_token_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Password> {
+        self.can_generate()?;
+        match self.size {
+            TokenSize::Bytes(bytes) => {
+                let mut random = vec![0u8; bytes];
+                csp_rng.fill_bytes(&mut random);
+                let mut encoded = self.encoding.encode(&random, self.padding);
+                let token = Password::from_chars(&encoded);
+                fill_volatile(&mut random, 0);
+                fill_volatile(&mut encoded, PASSWORD_FILL_CHARACTER);
+                token
+            }
+            TokenSize::Bits(_) => {
+                let alphabet: Vec<char> = self.encoding.alphabet().chars().collect();
+                Password::generate_with_rng(self.get_len(), &alphabet, csp_rng)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn encode(encoding: Encoding, input: &str, padding: bool) -> String {
+        encoding.encode(input.as_bytes(), padding).into_iter().collect()
+    }
+
+    #[test]
+    fn rfc4648_test() {
+        let inputs = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+        let hex = ["", "66", "666f", "666f6f", "666f6f62", "666f6f6261", "666f6f626172"];
+        let base32 =
+            ["", "MY======", "MZXQ====", "MZXW6===", "MZXW6YQ=", "MZXW6YTB", "MZXW6YTBOI======"];
+        let base64 = ["", "Zg==", "Zm8=", "Zm9v", "Zm9vYg==", "Zm9vYmE=", "Zm9vYmFy"];
+        for (i, input) in inputs.iter().enumerate() {
+            assert_eq!(encode(Encoding::Hex, input, true), hex[i]);
+            assert_eq!(encode(Encoding::Base32, input, true), base32[i]);
+            assert_eq!(encode(Encoding::Base32, input, false), base32[i].trim_end_matches('='));
+            assert_eq!(encode(Encoding::Base64, input, true), base64[i]);
+            assert_eq!(encode(Encoding::Base64, input, false), base64[i].trim_end_matches('='));
+        }
+    }
+
+    #[test]
+    fn base64url_test() {
+        let bytes = [0xfb, 0xff, 0xbf];
+        assert_eq!(Encoding::Base64.encode(&bytes, true).into_iter().collect::<String>(), "+/+/");
+        assert_eq!(
+            Encoding::Base64Url.encode(&bytes, true).into_iter().collect::<String>(),
+            "-_-_"
+        );
+        let bytes = [0xfb];
+        assert_eq!(
+            Encoding::Base64Url.encode(&bytes, true).into_iter().collect::<String>(),
+            "-w=="
+        );
+        assert_eq!(Encoding::Base64Url.encode(&bytes, false).into_iter().collect::<String>(), "-w");
+    }
+
+    #[test]
+    fn base58_test() {
+        assert_eq!(encode(Encoding::Base58, "", true), "");
+        assert_eq!(encode(Encoding::Base58, "Hello World!", true), "2NEpo7TZRRrLZSi2U");
+        let bytes = [0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd];
+        assert_eq!(
+            Encoding::Base58.encode(&bytes, true).into_iter().collect::<String>(),
+            "11233QC4"
+        );
+    }
+
+    #[test]
+    fn base62_test() {
+        assert_eq!(encode(Encoding::Base62, "", true), "");
+        assert_eq!(Encoding::Base62.encode(&[0, 61], true).into_iter().collect::<String>(), "0z");
+        assert_eq!(Encoding::Base62.encode(&[1, 0], true).into_iter().collect::<String>(), "48");
+        let bytes = [0xff; 4];
+        assert_eq!(Encoding::Base62.encode(&bytes, true).into_iter().collect::<String>(), "4gfFC3");
+    }
+
+    #[test]
+    fn crockford_test() {
+        // same bits as base32 "MZXW6YTBOI", without padding
+        assert_eq!(encode(Encoding::Crockford, "foobar", true), "CSQPYRK1E8");
+        assert_eq!(encode(Encoding::Crockford, "f", true), "CR");
+    }
+
+    #[test]
+    fn parse_encoding_test() {
+        for encoding in Encoding::ALL.iter() {
+            assert_eq!(encoding.name().parse::<Encoding>().unwrap(), *encoding);
+        }
+        assert_eq!("Base64URL".parse::<Encoding>().unwrap(), Encoding::Base64Url);
+        assert!("base85".parse::<Encoding>().is_err());
+    }
+
+    #[test]
+    fn generate_bytes_test() {
+        let generator = TokenGenerator::new(TokenSize::Bytes(32), Encoding::Hex, false);
+        let token = generator.generate_token().unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(generator.get_len(), 64);
+        assert!(token.iter().all(|c| HEX_ALPHABET.contains(*c)));
+        assert_eq!(generator.entropy(), 256.0);
+
+        let generator = TokenGenerator::new(TokenSize::Bytes(16), Encoding::Base64, true);
+        let token = generator.generate_token().unwrap();
+        assert_eq!(token.len(), 24);
+        assert!(token.to_string().ends_with("=="));
+        let generator = TokenGenerator::new(TokenSize::Bytes(16), Encoding::Base64Url, false);
+        assert_eq!(generator.generate_token().unwrap().len(), 22);
+    }
+
+    #[test]
+    fn generate_bits_test() {
+        let generator = TokenGenerator::new(TokenSize::Bits(128), Encoding::Base58, true);
+        let token = generator.generate_token().unwrap();
+        assert_eq!(token.len(), 22);
+        assert!(token.iter().all(|c| BASE58_ALPHABET.contains(*c)));
+        assert!(generator.entropy() >= 128.0);
+
+        let generator = TokenGenerator::new(TokenSize::Bits(130), Encoding::Crockford, true);
+        assert_eq!(generator.generate_token().unwrap().len(), 26);
+        assert_eq!(generator.entropy(), 130.0);
+    }
+
+    #[test]
+    fn generate_with_rng_test() {
+        let generator = TokenGenerator::default();
+        let a = generator.generate_token_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        let b = generator.generate_token_with_rng(&mut StdRng::seed_from_u64(0)).unwrap();
+        assert_eq!(a.to_string(), b.to_string());
+        assert_eq!(a.len(), 43);
+    }
+
+    #[test]
+    fn invalid_size_test() {
+        let generator = TokenGenerator::new(TokenSize::Bytes(0), Encoding::Hex, false);
+        assert!(generator.generate_token().is_err());
+        let generator =
+            TokenGenerator::new(TokenSize::Bytes(TOKEN_MAX_BYTES), Encoding::Hex, false);
+        assert_eq!(generator.generate_token().unwrap().len(), 1024);
+        let generator =
+            TokenGenerator::new(TokenSize::Bytes(TOKEN_MAX_BYTES + 1), Encoding::Hex, false);
+        assert!(generator.generate_token().is_err());
+    }
+}
@@ -0,0 +1,433 @@
+use alloc::collections::BTreeMap;
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use core::fmt;
+use rand::{CryptoRng, RngCore};
+use std::{fs, io::Write, path::Path};
+
+use super::{
+    generator::PasswordGenerator,
+    otp::unix_time,
+    password::{fill_volatile, zero_string, Password},
+    VaultError, PASSWORD_FILL_CHARACTER,
+};
+
+/// magic bytes at the beginning of vault file
+pub const VAULT_MAGIC: &[u8] = b"passgen-vault";
+
+/// version of vault file format
+pub const VAULT_VERSION: u8 = 1;
+
+/// bytes of random salt of Argon2id
+pub const VAULT_SALT_BYTES: usize = 16;
+
+/// bytes of random nonce of XChaCha20-Poly1305, which is safe to be drawn randomly for each save
+pub const VAULT_NONCE_BYTES: usize = 24;
+
+/// bytes of key derived from passphrase
+pub const VAULT_KEY_BYTES: usize = 32;
+
+/// header is magic, version, Argon2id parameters, salt and nonce, authenticated as associated data
+pub const VAULT_HEADER_BYTES: usize = 13 + 1 + 12 + VAULT_SALT_BYTES + VAULT_NONCE_BYTES;
+
+/// max memory cost in KiB of vault file, not to exhaust memory by broken file
+pub const VAULT_MAX_M_COST: u32 = 4 * 1024 * 1024;
+
+/// max iterations of vault file, not to hang on key derivation of broken file
+pub const VAULT_MAX_T_COST: u32 = 64;
+
+/// max parallelism of vault file, not to spawn lanes without limit by broken file
+pub const VAULT_MAX_P_COST: u32 = 64;
+
+/// Argon2id parameters to derive key from passphrase
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VaultKdf {
+    /// memory cost in KiB
+    pub m_cost: u32,
+    pub t_cost: u32,
+    pub p_cost: u32,
+}
+
+impl Default for VaultKdf {
+    /// OWASP recommended parameters, as password hash
+    fn default() -> Self {
+        Self {
+            m_cost: Params::DEFAULT_M_COST,
+            t_cost: Params::DEFAULT_T_COST,
+            p_cost: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl VaultKdf {
+    /// derive key from passphrase and salt
+    pub fn derive_key(
+        &self,
+        passphrase: &str,
+        salt: &[u8],
+    ) -> anyhow::Result<[u8; VAULT_KEY_BYTES]> {
+        if self.m_cost > VAULT_MAX_M_COST
+            || self.t_cost > VAULT_MAX_T_COST
+            || self.p_cost > VAULT_MAX_P_COST
+        {
+            Err(VaultError::InvalidFormat)?
+        }
+        let params = Params::new(self.m_cost, self.t_cost, self.p_cost, Some(VAULT_KEY_BYTES))
+            .map_err(|_| VaultError::InvalidFormat)?;
+        let mut key = [0; VAULT_KEY_BYTES];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+            .map_err(|_| VaultError::InvalidFormat)?;
+        Ok(key)
+    }
+}
+
+/// secret stored in vault, with settings which generated it.
+/// secret is zero-filled at dropped time
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct VaultEntry {
+    secret: String,
+    pub settings: PasswordGenerator,
+    /// unix time when the entry is added
+    pub created: u64,
+    /// unix time when the secret is generated, by add or rotate
+    pub updated: u64,
+}
+
+impl Drop for VaultEntry {
+    fn drop(&mut self) {
+        zero_string(&mut self.secret);
+    }
+}
+
+impl fmt::Debug for VaultEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VaultEntry")
+            .field("secret", &"********")
+            .field("settings", &self.settings)
+            .field("created", &self.created)
+            .field("updated", &self.updated)
+            .finish()
+    }
+}
+
+impl VaultEntry {
+    /// generate new entry by settings
+    pub fn generate(settings: PasswordGenerator) -> anyhow::Result<Self> {
+        let password = settings.generate_password()?;
+        let now = unix_time();
+        Ok(Self { secret: password.to_string(), settings, created: now, updated: now })
+    }
+
+    /// return stored secret
+    pub fn secret(&self) -> anyhow::Result<Password> {
+        let mut chars: Vec<char> = self.secret.chars().collect();
+        let secret = Password::from_chars(&chars);
+        fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+        secret
+    }
+}
+
+/// Local vault of generated secrets, which is a single file encrypted by XChaCha20-Poly1305
+/// with a key derived from passphrase by Argon2id. Entries are encrypted as a whole in memory,
+/// so plaintext is never written to disk. Key is zero-filled at dropped time.
+pub struct Vault {
+    key: [u8; VAULT_KEY_BYTES],
+    salt: [u8; VAULT_SALT_BYTES],
+    kdf: VaultKdf,
+    entries: BTreeMap<String, VaultEntry>,
+}
+
+impl Drop for Vault {
+    fn drop(&mut self) {
+        fill_volatile(&mut self.key, 0);
+    }
+}
+
+impl fmt::Debug for Vault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vault")
+            .field("key", &"********")
+            .field("kdf", &self.kdf)
+            .field("entries", &self.entries)
+            .finish()
+    }
+}
+
+impl Vault {
+    /// create new empty vault, whose key is derived from passphrase with random salt
+    pub fn create(passphrase: &str) -> anyhow::Result<Self> {
+        Self::create_with_rng(passphrase, VaultKdf::default(), &mut rand::thread_rng())
+    }
+
+    /// create new empty vault with given Argon2id parameters and CSPRNG
+    pub fn create_with_rng<R: RngCore + CryptoRng>(
+        passphrase: &str,
+        kdf: VaultKdf,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Self> {
+        let mut salt = [0; VAULT_SALT_BYTES];
+        csp_rng.fill_bytes(&mut salt);
+        let key = kdf.derive_key(passphrase, &salt)?;
+        Ok(Self { key, salt, kdf, entries: BTreeMap::new() })
+    }
+
+    /// decrypt vault from bytes of vault file
+    pub fn open(bytes: &[u8], passphrase: &str) -> anyhow::Result<Self> {
+        if bytes.len() < VAULT_HEADER_BYTES || !bytes.starts_with(VAULT_MAGIC) {
+            Err(VaultError::InvalidFormat)?
+        }
+        let (header, ciphertext) = bytes.split_at(VAULT_HEADER_BYTES);
+        let (version, rest) = header[VAULT_MAGIC.len()..].split_at(1);
+        if version[0] != VAULT_VERSION {
+            Err(VaultError::UnsupportedVersion(version[0]))?
+        }
+        let (params, rest) = rest.split_at(12);
+        let (salt, nonce) = rest.split_at(VAULT_SALT_BYTES);
+        let u32_at =
+            |i: usize| u32::from_le_bytes([params[i], params[i + 1], params[i + 2], params[i + 3]]);
+        let kdf = VaultKdf { m_cost: u32_at(0), t_cost: u32_at(4), p_cost: u32_at(8) };
+        let key = kdf.derive_key(passphrase, salt)?;
+        let mut vault = Self { key, salt: [0; VAULT_SALT_BYTES], kdf, entries: BTreeMap::new() };
+        vault.salt.copy_from_slice(salt);
+
+        let cipher = XChaCha20Poly1305::new(&vault.key.into());
+        let payload = Payload { msg: ciphertext, aad: header };
+        let mut plaintext = cipher
+            .decrypt(XNonce::from_slice(nonce), payload)
+            .map_err(|_| VaultError::DecryptionFailed)?;
+        let entries = serde_json::from_slice(&plaintext);
+        fill_volatile(&mut plaintext, 0);
+        vault.entries = entries.map_err(|_| VaultError::InvalidFormat)?;
+        Ok(vault)
+    }
+
+    /// read vault file and decrypt it
+    pub fn load(path: impl AsRef<Path>, passphrase: &str) -> anyhow::Result<Self> {
+        Self::open(&fs::read(path)?, passphrase)
+    }
+
+    /// encrypt vault with new random nonce, and return bytes of vault file
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        self.to_bytes_with_rng(&mut rand::thread_rng())
+    }
+
+    /// encrypt vault with new random nonce drawn from given CSPRNG
+    pub fn to_bytes_with_rng<R: RngCore + CryptoRng>(
+        &self,
+        csp_rng: &mut R,
+    ) -> anyhow::Result<Vec<u8>> {
+        let mut nonce = [0; VAULT_NONCE_BYTES];
+        csp_rng.fill_bytes(&mut nonce);
+        let mut header = Vec::with_capacity(VAULT_HEADER_BYTES);
+        header.extend_from_slice(VAULT_MAGIC);
+        header.push(VAULT_VERSION);
+        for param in [self.kdf.m_cost, self.kdf.t_cost, self.kdf.p_cost].iter() {
+            header.extend_from_slice(&param.to_le_bytes());
+        }
+        header.extend_from_slice(&self.salt);
+        header.extend_from_slice(&nonce);
+
+        let mut plaintext = serde_json::to_vec(&self.entries)?;
+        let cipher = XChaCha20Poly1305::new(&self.key.into());
+        let payload = Payload { msg: &plaintext, aad: &header };
+        let ciphertext = cipher.encrypt(XNonce::from_slice(&nonce), payload);
+        fill_volatile(&mut plaintext, 0);
+        let ciphertext = ciphertext.map_err(|_| VaultError::EncryptionFailed)?;
+        Ok([header, ciphertext].concat())
+    }
+
+    /// encrypt vault and write it to file atomically, readable only by owner
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        let bytes = self.to_bytes()?;
+        let file_name = path.file_name().ok_or(VaultError::InvalidPath)?.to_string_lossy();
+        let temporary = path.with_file_name(format!(".{}.tmp", file_name));
+        let _ = fs::remove_file(&temporary);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&temporary)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&temporary, path)?;
+        Ok(())
+    }
+
+    /// return Argon2id parameters of this vault
+    pub fn kdf(&self) -> VaultKdf {
+        self.kdf
+    }
+
+    /// return iterator of names and entries, in order of names
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &VaultEntry)> {
+        self.entries.iter().map(|(name, entry)| (&name[..], entry))
+    }
+
+    /// return entry of name
+    pub fn get(&self, name: &str) -> anyhow::Result<&VaultEntry> {
+        Ok(self.entries.get(name).ok_or_else(|| VaultError::EntryNotFound(name.to_string()))?)
+    }
+
+    /// generate secret by settings and store it with settings as new entry
+    pub fn add(&mut self, name: &str, settings: PasswordGenerator) -> anyhow::Result<Password> {
+        if name.is_empty() {
+            Err(VaultError::EmptyName)?
+        } else if self.entries.contains_key(name) {
+            Err(VaultError::EntryExists(name.to_string()))?
+        }
+        let entry = VaultEntry::generate(settings)?;
+        let secret = entry.secret()?;
+        self.entries.insert(name.to_string(), entry);
+        Ok(secret)
+    }
+
+    /// regenerate secret of entry by its recorded settings
+    pub fn rotate(&mut self, name: &str) -> anyhow::Result<Password> {
+        let entry = self
+            .entries
+            .get_mut(name)
+            .ok_or_else(|| VaultError::EntryNotFound(name.to_string()))?;
+        let password = entry.settings.generate_password()?;
+        zero_string(&mut entry.secret);
+        entry.secret = password.to_string();
+        entry.updated = unix_time();
+        Ok(password)
+    }
+
+    /// remove entry of name
+    pub fn remove(&mut self, name: &str) -> anyhow::Result<()> {
+        self.entries.remove(name).ok_or_else(|| VaultError::EntryNotFound(name.to_string()))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::symbol::MarkSet;
+
+    /// small parameters for test, default parameters take too long without optimization
+    const TEST_KDF: VaultKdf = VaultKdf { m_cost: 64, t_cost: 1, p_cost: 1 };
+
+    fn test_vault(passphrase: &str) -> Vault {
+        Vault::create_with_rng(passphrase, TEST_KDF, &mut StdRng::seed_from_u64(0)).unwrap()
+    }
+
+    #[test]
+    fn add_and_open_test() {
+        let mut vault = test_vault("correct horse");
+        let mut settings = PasswordGenerator::default();
+        settings.len = 32;
+        settings.mark = MarkSet::new();
+        let secret = vault.add("github", settings).unwrap().to_string();
+        vault.add("mail", PasswordGenerator::default()).unwrap();
+
+        let bytes = vault.to_bytes().unwrap();
+        assert!(bytes.starts_with(VAULT_MAGIC));
+        // neither secret, name nor settings are written in plaintext
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(!text.contains(&secret) && !text.contains("github") && !text.contains("use_lower"));
+
+        let opened = Vault::open(&bytes, "correct horse").unwrap();
+        assert_eq!(opened.kdf(), TEST_KDF);
+        let names: Vec<_> = opened.entries().map(|(name, _)| name).collect();
+        assert_eq!(names, ["github", "mail"]);
+        let entry = opened.get("github").unwrap();
+        assert_eq!(entry.secret().unwrap().to_string(), secret);
+        assert_eq!(entry.settings.len, 32);
+        assert!(entry.settings.mark.is_empty());
+        assert_eq!(entry.created, entry.updated);
+        assert_eq!(format!("{:?}", entry).matches(&secret[..]).count(), 0);
+    }
+
+    #[test]
+    fn nonce_test() {
+        let vault = test_vault("correct horse");
+        let (a, b) = (vault.to_bytes().unwrap(), vault.to_bytes().unwrap());
+        assert_ne!(a, b);
+        assert_eq!(
+            a[..VAULT_HEADER_BYTES - VAULT_NONCE_BYTES],
+            b[..VAULT_HEADER_BYTES - VAULT_NONCE_BYTES]
+        );
+    }
+
+    #[test]
+    fn wrong_passphrase_test() {
+        let mut vault = test_vault("correct horse");
+        vault.add("github", PasswordGenerator::default()).unwrap();
+        let bytes = vault.to_bytes().unwrap();
+        let error = Vault::open(&bytes, "battery staple").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::DecryptionFailed)));
+
+        // header is authenticated, so parameters cannot be changed
+        let mut tampered = bytes.clone();
+        tampered[VAULT_MAGIC.len() + 5] ^= 1;
+        assert!(Vault::open(&tampered, "correct horse").is_err());
+        let mut tampered = bytes.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        let error = Vault::open(&tampered, "correct horse").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::DecryptionFailed)));
+
+        let error = Vault::open(b"not a vault", "correct horse").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::InvalidFormat)));
+        for (offset, cost) in [(0, VAULT_MAX_M_COST), (4, VAULT_MAX_T_COST), (8, VAULT_MAX_P_COST)]
+        {
+            let mut expensive = bytes.clone();
+            let at = VAULT_MAGIC.len() + 1 + offset;
+            expensive[at..at + 4].copy_from_slice(&(cost + 1).to_le_bytes());
+            let error = Vault::open(&expensive, "correct horse").unwrap_err();
+            assert!(matches!(error.downcast_ref(), Some(VaultError::InvalidFormat)));
+        }
+        let mut future = bytes;
+        future[VAULT_MAGIC.len()] = 2;
+        let error = Vault::open(&future, "correct horse").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::UnsupportedVersion(2))));
+    }
+
+    #[test]
+    fn rotate_and_remove_test() {
+        let mut vault = test_vault("correct horse");
+        let mut settings = PasswordGenerator::default();
+        settings.len = 16;
+        let old = vault.add("github", settings).unwrap().to_string();
+        let new = vault.rotate("github").unwrap().to_string();
+        assert_ne!(old, new);
+        assert_eq!(new.chars().count(), 16);
+        assert_eq!(vault.get("github").unwrap().secret().unwrap().to_string(), new);
+
+        let error = vault.add("github", PasswordGenerator::default()).unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::EntryExists(_))));
+        vault.remove("github").unwrap();
+        let error = vault.get("github").unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(VaultError::EntryNotFound(_))));
+        assert!(vault.rotate("github").is_err() && vault.remove("github").is_err());
+        assert!(vault.add("", PasswordGenerator::default()).is_err());
+    }
+
+    #[test]
+    fn save_and_load_test() {
+        let dir = std::env::temp_dir().join(format!("passgen-vault-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("vault");
+        let mut vault = test_vault("correct horse");
+        let secret = vault.add("github", PasswordGenerator::default()).unwrap().to_string();
+        vault.save(&path).unwrap();
+        vault.save(&path).unwrap();
+        assert!(!dir.join(".vault.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        let loaded = Vault::load(&path, "correct horse").unwrap();
+        assert_eq!(loaded.get("github").unwrap().secret().unwrap().to_string(), secret);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
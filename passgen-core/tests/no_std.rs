@@ -0,0 +1,15 @@
+use std::process::Command;
+
+/// build `no_std` crate, which defines its own panic handler, so it fails if std is linked
+#[test]
+fn no_std_build_test() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let output = Command::new(env!("CARGO"))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(format!("{}/no_std/Cargo.toml", manifest_dir))
+        .env("CARGO_TARGET_DIR", format!("{}/../target/no_std", manifest_dir))
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
@@ -0,0 +1,208 @@
+#ifndef PASSGEN_H
+#define PASSGEN_H
+
+/* This file is generated by cbindgen from passgen-ffi/src/lib.rs, do not edit it. */
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * buffer of this size can hold any generated password in UTF-8 and NUL
+ */
+#define PASSGEN_BUFFER_SIZE 513
+
+/**
+ * result of C API, these values are stable
+ */
+typedef enum PassgenStatus {
+  PASSGEN_STATUS_OK = 0,
+  PASSGEN_STATUS_NULL_POINTER = 1,
+  PASSGEN_STATUS_INVALID_UTF8 = 2,
+  PASSGEN_STATUS_BUFFER_TOO_SMALL = 3,
+  PASSGEN_STATUS_UNKNOWN_NAME = 4,
+  PASSGEN_STATUS_INVALID_MARK = 5,
+  PASSGEN_STATUS_EMPTY_LENGTH = 16,
+  PASSGEN_STATUS_TOO_SHORT_LENGTH = 17,
+  PASSGEN_STATUS_TOO_LONG_LENGTH = 18,
+  PASSGEN_STATUS_EMPTY_SYMBOL = 19,
+  PASSGEN_STATUS_TOO_MANY_REDRAW = 20,
+  PASSGEN_STATUS_LOW_ENTROPY = 21,
+  PASSGEN_STATUS_TOO_FEW_SWITCHES = 22,
+  PASSGEN_STATUS_INVALID_CHAR = 23,
+  PASSGEN_STATUS_KEYBOARD_WITH_BYTES = 24,
+  PASSGEN_STATUS_TOO_MANY_CLASSES = 25,
+  PASSGEN_STATUS_PASSWORD_TOO_LONG = 26,
+  PASSGEN_STATUS_UNKNOWN = 255,
+} PassgenStatus;
+
+/**
+ * opaque handle of password generator
+ */
+typedef struct PassgenGenerator PassgenGenerator;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Return new generator with default setting, it should be freed by `passgen_generator_free`.
+ */
+struct PassgenGenerator *passgen_generator_new(void);
+
+/**
+ * Zero context of generator, and free it. Null is ignored.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and must not be used after this.
+ */
+void passgen_generator_free(struct PassgenGenerator *generator);
+
+/**
+ * Set password length, it is checked when generating.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`.
+ */
+enum PassgenStatus passgen_generator_set_len(struct PassgenGenerator *generator, size_t len);
+
+/**
+ * Set whether password uses lower case alphabet.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`.
+ */
+enum PassgenStatus passgen_generator_set_use_lower(struct PassgenGenerator *generator,
+                                                   bool use_lower);
+
+/**
+ * Set whether password uses upper case alphabet.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`.
+ */
+enum PassgenStatus passgen_generator_set_use_upper(struct PassgenGenerator *generator,
+                                                   bool use_upper);
+
+/**
+ * Set whether password uses numeric.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`.
+ */
+enum PassgenStatus passgen_generator_set_use_numeric(struct PassgenGenerator *generator,
+                                                     bool use_numeric);
+
+/**
+ * Set marks such as `"!@#"`, empty string means no mark.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `marks` must be NUL terminated.
+ */
+enum PassgenStatus passgen_generator_set_mark(struct PassgenGenerator *generator,
+                                              const char *marks);
+
+/**
+ * Set marks to preset such as `"shell"`.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `preset` must be NUL terminated.
+ */
+enum PassgenStatus passgen_generator_set_mark_preset(struct PassgenGenerator *generator,
+                                                     const char *preset);
+
+/**
+ * Set custom characters, which are NFC normalized.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `addition` must be NUL terminated.
+ */
+enum PassgenStatus passgen_generator_set_addition(struct PassgenGenerator *generator,
+                                                  const char *addition);
+
+/**
+ * Set word filter, with bundled word lists or not, and newline separated words or null.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `words` must be NUL terminated
+ * or null.
+ */
+enum PassgenStatus passgen_generator_set_filter(struct PassgenGenerator *generator,
+                                                bool bundled,
+                                                const char *words);
+
+/**
+ * Set context strings such as username, whose tokens must not appear in password.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `contexts` must be array of
+ * `count` NUL terminated strings.
+ */
+enum PassgenStatus passgen_generator_set_context(struct PassgenGenerator *generator,
+                                                 const char *const *contexts,
+                                                 size_t count);
+
+/**
+ * Set keyboard layout such as `"ios"`, or null to ignore layout. Negative max switches means
+ * minimizing switches keeping min entropy.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `layout` must be NUL terminated
+ * or null.
+ */
+enum PassgenStatus passgen_generator_set_keyboard(struct PassgenGenerator *generator,
+                                                  const char *layout,
+                                                  int64_t max_switches,
+                                                  double min_entropy);
+
+/**
+ * Set unicode classes such as `"greek"`, which password uses too.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `classes` must be array of
+ * `count` NUL terminated strings.
+ */
+enum PassgenStatus passgen_generator_set_unicode(struct PassgenGenerator *generator,
+                                                 const char *const *classes,
+                                                 size_t count);
+
+/**
+ * Set unit of password length, `"chars"`, `"graphemes"` or `"bytes"`.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `unit` must be NUL terminated.
+ */
+enum PassgenStatus passgen_generator_set_unit(struct PassgenGenerator *generator, const char *unit);
+
+/**
+ * Generate password into buffer as NUL terminated UTF-8, and set its length without NUL into
+ * `written` if it is not null. `PASSGEN_BUFFER_SIZE` bytes are always enough.
+ *
+ * # Safety
+ * `generator` must be returned by `passgen_generator_new`, and `buffer` must be writable for
+ * `buffer_len` bytes.
+ */
+enum PassgenStatus passgen_generate(const struct PassgenGenerator *generator,
+                                    char *buffer,
+                                    size_t buffer_len,
+                                    size_t *written);
+
+/**
+ * Zero buffer such as generated password, it is not elided by compiler unlike `memset`.
+ *
+ * # Safety
+ * `buffer` must be writable for `buffer_len` bytes, or null.
+ */
+void passgen_zero(char *buffer, size_t buffer_len);
+
+/**
+ * Return static description of status, which must not be freed.
+ * Value which is not any status is described as unknown status.
+ */
+const char *passgen_status_message(int status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* PASSGEN_H */
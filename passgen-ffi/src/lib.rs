@@ -0,0 +1,561 @@
+//! C API of passgen, built on `PasswordGenerator`.
+//!
+//! Every function except constructor and free returns `PassgenStatus`.
+//! Strings are NUL terminated UTF-8, and generated password is written into caller's buffer.
+
+use passgen_core::{
+    filter::WordFilter,
+    generator::PasswordGenerator,
+    keyboard::{KeyboardLayout, KeyboardSetting},
+    password::{fill_volatile, zero_string, LengthUnit, Password},
+    symbol::{self, MarkPreset, MarkSet, UnicodeClass},
+    GeneratorError, ParseError, PasswordError,
+};
+use std::{
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+    slice,
+};
+
+/// buffer of this size can hold any generated password in UTF-8 and NUL
+pub const PASSGEN_BUFFER_SIZE: usize = 513;
+
+/// opaque handle of password generator
+pub struct PassgenGenerator {
+    generator: PasswordGenerator,
+}
+
+impl Drop for PassgenGenerator {
+    fn drop(&mut self) {
+        for context in self.generator.context.iter_mut() {
+            // context such as username is zeroed, same as password
+            zero_string(context);
+        }
+    }
+}
+
+/// result of C API, these values are stable
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PassgenStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    BufferTooSmall = 3,
+    UnknownName = 4,
+    InvalidMark = 5,
+    EmptyLength = 16,
+    TooShortLength = 17,
+    TooLongLength = 18,
+    EmptySymbol = 19,
+    TooManyRedraw = 20,
+    LowEntropy = 21,
+    TooFewSwitches = 22,
+    InvalidChar = 23,
+    KeyboardWithBytes = 24,
+    TooManyClasses = 25,
+    PasswordTooLong = 26,
+    Unknown = 255,
+}
+
+impl From<anyhow::Error> for PassgenStatus {
+    fn from(err: anyhow::Error) -> Self {
+        if let Some(err) = err.downcast_ref::<GeneratorError>() {
+            match err {
+                GeneratorError::EmptyLength => PassgenStatus::EmptyLength,
+                GeneratorError::TooShortLength(_) => PassgenStatus::TooShortLength,
+                GeneratorError::TooLongLength(_) => PassgenStatus::TooLongLength,
+                GeneratorError::EmptySymbol => PassgenStatus::EmptySymbol,
+                GeneratorError::TooManyRedraw(_) => PassgenStatus::TooManyRedraw,
+                GeneratorError::LowEntropy(_, _) => PassgenStatus::LowEntropy,
+                GeneratorError::TooFewSwitches(_, _) => PassgenStatus::TooFewSwitches,
+                GeneratorError::InvalidChar(_) => PassgenStatus::InvalidChar,
+                GeneratorError::KeyboardWithBytes => PassgenStatus::KeyboardWithBytes,
+                GeneratorError::TooManyClasses(_, _) => PassgenStatus::TooManyClasses,
+            }
+        } else if err.is::<PasswordError>() {
+            PassgenStatus::PasswordTooLong
+        } else {
+            PassgenStatus::Unknown
+        }
+    }
+}
+
+impl From<ParseError> for PassgenStatus {
+    fn from(_: ParseError) -> Self {
+        PassgenStatus::UnknownName
+    }
+}
+
+impl PassgenStatus {
+    /// return status of raw value given from C, or None if it is not any status
+    pub fn from_raw(status: c_int) -> Option<Self> {
+        match status {
+            0 => Some(PassgenStatus::Ok),
+            1 => Some(PassgenStatus::NullPointer),
+            2 => Some(PassgenStatus::InvalidUtf8),
+            3 => Some(PassgenStatus::BufferTooSmall),
+            4 => Some(PassgenStatus::UnknownName),
+            5 => Some(PassgenStatus::InvalidMark),
+            16 => Some(PassgenStatus::EmptyLength),
+            17 => Some(PassgenStatus::TooShortLength),
+            18 => Some(PassgenStatus::TooLongLength),
+            19 => Some(PassgenStatus::EmptySymbol),
+            20 => Some(PassgenStatus::TooManyRedraw),
+            21 => Some(PassgenStatus::LowEntropy),
+            22 => Some(PassgenStatus::TooFewSwitches),
+            23 => Some(PassgenStatus::InvalidChar),
+            24 => Some(PassgenStatus::KeyboardWithBytes),
+            25 => Some(PassgenStatus::TooManyClasses),
+            26 => Some(PassgenStatus::PasswordTooLong),
+            255 => Some(PassgenStatus::Unknown),
+            _ => None,
+        }
+    }
+
+    /// return description of this status
+    pub fn message(&self) -> &'static CStr {
+        let message: &[u8] = match self {
+            PassgenStatus::Ok => b"ok\0",
+            PassgenStatus::NullPointer => b"null pointer is given\0",
+            PassgenStatus::InvalidUtf8 => b"given string is not valid UTF-8\0",
+            PassgenStatus::BufferTooSmall => b"buffer is too small for password\0",
+            PassgenStatus::UnknownName => b"unknown name of preset, layout, class or unit\0",
+            PassgenStatus::InvalidMark => b"given character cannot be used as mark\0",
+            PassgenStatus::EmptyLength => b"password should not be empty\0",
+            PassgenStatus::TooShortLength => b"password is too short\0",
+            PassgenStatus::TooLongLength => b"password is too long\0",
+            PassgenStatus::EmptySymbol => b"no available symbol\0",
+            PassgenStatus::TooManyRedraw => b"too many redraws by word filter or context\0",
+            PassgenStatus::LowEntropy => b"password entropy is too low\0",
+            PassgenStatus::TooFewSwitches => b"max layer switches is too few\0",
+            PassgenStatus::InvalidChar => b"control, format, unassigned or combining character\0",
+            PassgenStatus::KeyboardWithBytes => b"keyboard layout cannot be used with bytes\0",
+            PassgenStatus::TooManyClasses => b"selected classes do not fit in password length\0",
+            PassgenStatus::PasswordTooLong => b"password does not fit in its buffer\0",
+            PassgenStatus::Unknown => b"unknown error\0",
+        };
+        CStr::from_bytes_with_nul(message).expect("message is terminated by NUL")
+    }
+}
+
+/// run setter of generator, then convert its result into status
+unsafe fn with_generator<F>(generator: *mut PassgenGenerator, f: F) -> PassgenStatus
+where
+    F: FnOnce(&mut PasswordGenerator) -> Result<(), PassgenStatus>,
+{
+    match generator.as_mut() {
+        Some(handle) => f(&mut handle.generator).err().unwrap_or(PassgenStatus::Ok),
+        None => PassgenStatus::NullPointer,
+    }
+}
+
+/// borrow NUL terminated UTF-8 string
+unsafe fn to_str<'a>(s: *const c_char) -> Result<&'a str, PassgenStatus> {
+    if s.is_null() {
+        Err(PassgenStatus::NullPointer)
+    } else {
+        CStr::from_ptr(s).to_str().map_err(|_| PassgenStatus::InvalidUtf8)
+    }
+}
+
+/// borrow array of NUL terminated UTF-8 strings
+unsafe fn to_strs<'a>(
+    s: *const *const c_char,
+    count: usize,
+) -> Result<Vec<&'a str>, PassgenStatus> {
+    if count == 0 {
+        Ok(Vec::new())
+    } else if s.is_null() {
+        Err(PassgenStatus::NullPointer)
+    } else {
+        slice::from_raw_parts(s, count).iter().map(|&s| to_str(s)).collect()
+    }
+}
+
+/// write password into buffer as NUL terminated UTF-8, without intermediate String
+fn write_password(password: &Password, buffer: &mut [u8]) -> Result<usize, PassgenStatus> {
+    let mut written = 0;
+    for c in password.iter() {
+        if written + c.len_utf8() >= buffer.len() {
+            fill_volatile(&mut buffer[..written], 0);
+            return Err(PassgenStatus::BufferTooSmall);
+        }
+        written += c.encode_utf8(&mut buffer[written..]).len();
+    }
+    buffer[written] = 0;
+    Ok(written)
+}
+
+/// Return new generator with default setting, it should be freed by `passgen_generator_free`.
+#[no_mangle]
+pub extern "C" fn passgen_generator_new() -> *mut PassgenGenerator {
+    Box::into_raw(Box::new(PassgenGenerator { generator: PasswordGenerator::default() }))
+}
+
+/// Zero context of generator, and free it. Null is ignored.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and must not be used after this.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_free(generator: *mut PassgenGenerator) {
+    if !generator.is_null() {
+        drop(Box::from_raw(generator));
+    }
+}
+
+/// Set password length, it is checked when generating.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_len(
+    generator: *mut PassgenGenerator,
+    len: usize,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.len = len;
+        Ok(())
+    })
+}
+
+/// Set whether password uses lower case alphabet.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_use_lower(
+    generator: *mut PassgenGenerator,
+    use_lower: bool,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.use_lower = use_lower;
+        Ok(())
+    })
+}
+
+/// Set whether password uses upper case alphabet.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_use_upper(
+    generator: *mut PassgenGenerator,
+    use_upper: bool,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.use_upper = use_upper;
+        Ok(())
+    })
+}
+
+/// Set whether password uses numeric.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_use_numeric(
+    generator: *mut PassgenGenerator,
+    use_numeric: bool,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.use_numeric = use_numeric;
+        Ok(())
+    })
+}
+
+/// Set marks such as `"!@#"`, empty string means no mark.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `marks` must be NUL terminated.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_mark(
+    generator: *mut PassgenGenerator,
+    marks: *const c_char,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        let mut mark = MarkSet::new();
+        for c in to_str(marks)?.chars() {
+            if !mark.contains(&c) && !mark.insert(c) {
+                return Err(PassgenStatus::InvalidMark);
+            }
+        }
+        generator.mark = mark;
+        Ok(())
+    })
+}
+
+/// Set marks to preset such as `"shell"`.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `preset` must be NUL terminated.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_mark_preset(
+    generator: *mut PassgenGenerator,
+    preset: *const c_char,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.mark = MarkSet::from_preset(to_str(preset)?.parse::<MarkPreset>()?);
+        Ok(())
+    })
+}
+
+/// Set custom characters, which are NFC normalized.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `addition` must be NUL terminated.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_addition(
+    generator: *mut PassgenGenerator,
+    addition: *const c_char,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.addition = symbol::normalized_chars(to_str(addition)?);
+        Ok(())
+    })
+}
+
+/// Set word filter, with bundled word lists or not, and newline separated words or null.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `words` must be NUL terminated
+/// or null.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_filter(
+    generator: *mut PassgenGenerator,
+    bundled: bool,
+    words: *const c_char,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        let mut filter = if bundled { WordFilter::bundled() } else { WordFilter::new() };
+        if !words.is_null() {
+            filter.extend_from_text(to_str(words)?);
+        }
+        generator.filter = filter;
+        Ok(())
+    })
+}
+
+/// Set context strings such as username, whose tokens must not appear in password.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `contexts` must be array of
+/// `count` NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_context(
+    generator: *mut PassgenGenerator,
+    contexts: *const *const c_char,
+    count: usize,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        let contexts = to_strs(contexts, count)?;
+        for context in generator.context.iter_mut() {
+            zero_string(context);
+        }
+        generator.context = contexts.into_iter().map(String::from).collect();
+        Ok(())
+    })
+}
+
+/// Set keyboard layout such as `"ios"`, or null to ignore layout. Negative max switches means
+/// minimizing switches keeping min entropy.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `layout` must be NUL terminated
+/// or null.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_keyboard(
+    generator: *mut PassgenGenerator,
+    layout: *const c_char,
+    max_switches: i64,
+    min_entropy: f64,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.keyboard = if layout.is_null() {
+            None
+        } else {
+            let mut keyboard = KeyboardSetting::new(to_str(layout)?.parse::<KeyboardLayout>()?);
+            keyboard.max_switches =
+                if max_switches < 0 { None } else { Some(max_switches as usize) };
+            keyboard.min_entropy = min_entropy;
+            Some(keyboard)
+        };
+        Ok(())
+    })
+}
+
+/// Set unicode classes such as `"greek"`, which password uses too.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `classes` must be array of
+/// `count` NUL terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_unicode(
+    generator: *mut PassgenGenerator,
+    classes: *const *const c_char,
+    count: usize,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.unicode = to_strs(classes, count)?
+            .into_iter()
+            .map(str::parse::<UnicodeClass>)
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    })
+}
+
+/// Set unit of password length, `"chars"`, `"graphemes"` or `"bytes"`.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `unit` must be NUL terminated.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generator_set_unit(
+    generator: *mut PassgenGenerator,
+    unit: *const c_char,
+) -> PassgenStatus {
+    with_generator(generator, |generator| {
+        generator.unit = to_str(unit)?.parse::<LengthUnit>()?;
+        Ok(())
+    })
+}
+
+/// Generate password into buffer as NUL terminated UTF-8, and set its length without NUL into
+/// `written` if it is not null. `PASSGEN_BUFFER_SIZE` bytes are always enough.
+///
+/// # Safety
+/// `generator` must be returned by `passgen_generator_new`, and `buffer` must be writable for
+/// `buffer_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_generate(
+    generator: *const PassgenGenerator,
+    buffer: *mut c_char,
+    buffer_len: usize,
+    written: *mut usize,
+) -> PassgenStatus {
+    let generator = match generator.as_ref() {
+        Some(handle) => &handle.generator,
+        None => return PassgenStatus::NullPointer,
+    };
+    if buffer.is_null() {
+        return PassgenStatus::NullPointer;
+    } else if buffer_len == 0 {
+        return PassgenStatus::BufferTooSmall;
+    }
+    let buffer = slice::from_raw_parts_mut(buffer as *mut u8, buffer_len);
+    let result = generator
+        .generate_password()
+        .map_err(PassgenStatus::from)
+        .and_then(|password| write_password(&password, buffer));
+    match result {
+        Ok(len) => {
+            if let Some(written) = written.as_mut() {
+                *written = len;
+            }
+            PassgenStatus::Ok
+        }
+        Err(status) => {
+            buffer[0] = 0;
+            status
+        }
+    }
+}
+
+/// Zero buffer such as generated password, it is not elided by compiler unlike `memset`.
+///
+/// # Safety
+/// `buffer` must be writable for `buffer_len` bytes, or null.
+#[no_mangle]
+pub unsafe extern "C" fn passgen_zero(buffer: *mut c_char, buffer_len: usize) {
+    if !buffer.is_null() {
+        fill_volatile(slice::from_raw_parts_mut(buffer as *mut u8, buffer_len), 0);
+    }
+}
+
+/// Return static description of status, which must not be freed.
+/// Value which is not any status is described as unknown status.
+#[no_mangle]
+pub extern "C" fn passgen_status_message(status: c_int) -> *const c_char {
+    match PassgenStatus::from_raw(status) {
+        Some(status) => status.message().as_ptr(),
+        None => b"unknown status\0".as_ptr() as *const c_char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use passgen_core::PASSWORD_MAX_LENGTH;
+    use std::{ffi::CString, ptr};
+
+    #[test]
+    fn buffer_size_test() {
+        assert_eq!(PASSGEN_BUFFER_SIZE, PASSWORD_MAX_LENGTH * 4 + 1);
+    }
+
+    #[test]
+    fn generate_test() {
+        unsafe {
+            let generator = passgen_generator_new();
+            let mut buffer = [0 as c_char; PASSGEN_BUFFER_SIZE];
+            let mut written = 0;
+            let status =
+                passgen_generate(generator, buffer.as_mut_ptr(), buffer.len(), &mut written);
+            assert_eq!(status, PassgenStatus::Ok);
+            assert_eq!(written, 20);
+            assert_eq!(CStr::from_ptr(buffer.as_ptr()).to_bytes().len(), 20);
+            passgen_zero(buffer.as_mut_ptr(), buffer.len());
+            assert!(buffer.iter().all(|&b| b == 0));
+            let status = passgen_generate(generator, buffer.as_mut_ptr(), 20, &mut written);
+            assert_eq!(status, PassgenStatus::BufferTooSmall);
+            assert!(buffer.iter().all(|&b| b == 0));
+            passgen_generator_free(generator);
+        }
+    }
+
+    #[test]
+    fn setter_error_test() {
+        unsafe {
+            let generator = passgen_generator_new();
+            let ios = CString::new("ios").unwrap();
+            let dvorak = CString::new("dvorak").unwrap();
+            assert_eq!(
+                passgen_generator_set_keyboard(generator, dvorak.as_ptr(), -1, 64.),
+                PassgenStatus::UnknownName
+            );
+            assert_eq!(
+                passgen_generator_set_keyboard(generator, ios.as_ptr(), 0, 64.),
+                PassgenStatus::Ok
+            );
+            let mut buffer = [0 as c_char; PASSGEN_BUFFER_SIZE];
+            let status =
+                passgen_generate(generator, buffer.as_mut_ptr(), buffer.len(), ptr::null_mut());
+            assert_eq!(status, PassgenStatus::TooFewSwitches);
+            let letter = CString::new("a").unwrap();
+            assert_eq!(
+                passgen_generator_set_mark(generator, letter.as_ptr()),
+                PassgenStatus::InvalidMark
+            );
+            assert_eq!(passgen_generator_set_len(ptr::null_mut(), 8), PassgenStatus::NullPointer);
+            passgen_generator_free(generator);
+        }
+    }
+
+    #[test]
+    fn status_message_test() {
+        let message = |status| unsafe { CStr::from_ptr(passgen_status_message(status)) };
+        assert_eq!(
+            message(PassgenStatus::EmptySymbol as c_int).to_str().unwrap(),
+            "no available symbol"
+        );
+        assert_eq!(message(PassgenStatus::Unknown as c_int).to_str().unwrap(), "unknown error");
+        assert_eq!(message(26).to_str().unwrap(), "password does not fit in its buffer");
+        for status in 0..=255 {
+            if let Some(known) = PassgenStatus::from_raw(status) {
+                assert_eq!(known as c_int, status);
+            }
+        }
+        for status in [-1, 6, 27, 254, 256] {
+            assert_eq!(message(status).to_str().unwrap(), "unknown status");
+        }
+    }
+}
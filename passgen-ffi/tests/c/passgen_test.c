@@ -0,0 +1,85 @@
+/* C program using passgen C API, it is compiled and run by tests/c_api.rs */
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "passgen.h"
+
+static void generate_test(void) {
+    PassgenGenerator *generator = passgen_generator_new();
+    char buffer[PASSGEN_BUFFER_SIZE];
+    size_t written = 0;
+    assert(passgen_generate(generator, buffer, sizeof(buffer), &written) == PASSGEN_STATUS_OK);
+    assert(written == 20 && strlen(buffer) == 20);
+
+    assert(passgen_generator_set_len(generator, 8) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_use_upper(generator, false) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_use_numeric(generator, false) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_mark(generator, "") == PASSGEN_STATUS_OK);
+    assert(passgen_generate(generator, buffer, sizeof(buffer), &written) == PASSGEN_STATUS_OK);
+    for (size_t i = 0; i < written; i++) {
+        assert('a' <= buffer[i] && buffer[i] <= 'z');
+    }
+
+    passgen_zero(buffer, sizeof(buffer));
+    for (size_t i = 0; i < sizeof(buffer); i++) {
+        assert(buffer[i] == 0);
+    }
+    passgen_generator_free(generator);
+}
+
+static void setter_test(void) {
+    PassgenGenerator *generator = passgen_generator_new();
+    const char *contexts[] = {"alice", "alice@example.com"};
+    const char *classes[] = {"greek"};
+    assert(passgen_generator_set_context(generator, contexts, 2) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_filter(generator, true, "hunter\nletmein") == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_mark_preset(generator, "shell") == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_addition(generator, "\xc3\xa9") == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_unicode(generator, classes, 1) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_unit(generator, "graphemes") == PASSGEN_STATUS_OK);
+
+    char buffer[PASSGEN_BUFFER_SIZE];
+    assert(passgen_generate(generator, buffer, sizeof(buffer), NULL) == PASSGEN_STATUS_OK);
+    assert(strstr(buffer, "alice") == NULL);
+    passgen_zero(buffer, sizeof(buffer));
+
+    assert(passgen_generator_set_unicode(generator, NULL, 0) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_unit(generator, "chars") == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_keyboard(generator, "ios", -1, 64.0) == PASSGEN_STATUS_OK);
+    assert(passgen_generate(generator, buffer, sizeof(buffer), NULL) == PASSGEN_STATUS_OK);
+    passgen_zero(buffer, sizeof(buffer));
+    assert(passgen_generator_set_keyboard(generator, NULL, -1, 0.0) == PASSGEN_STATUS_OK);
+    passgen_generator_free(generator);
+}
+
+static void error_test(void) {
+    PassgenGenerator *generator = passgen_generator_new();
+    char buffer[PASSGEN_BUFFER_SIZE];
+    assert(passgen_generator_set_len(NULL, 8) == PASSGEN_STATUS_NULL_POINTER);
+    assert(passgen_generator_set_unit(generator, "words") == PASSGEN_STATUS_UNKNOWN_NAME);
+    assert(passgen_generator_set_addition(generator, "\xff") == PASSGEN_STATUS_INVALID_UTF8);
+    assert(passgen_generator_set_mark(generator, "a") == PASSGEN_STATUS_INVALID_MARK);
+
+    assert(passgen_generate(generator, buffer, 8, NULL) == PASSGEN_STATUS_BUFFER_TOO_SMALL);
+    assert(buffer[0] == 0);
+
+    assert(passgen_generator_set_len(generator, 7) == PASSGEN_STATUS_OK);
+    PassgenStatus status = passgen_generate(generator, buffer, sizeof(buffer), NULL);
+    assert(status == PASSGEN_STATUS_TOO_SHORT_LENGTH);
+    assert(strcmp(passgen_status_message(status), "password is too short") == 0);
+
+    assert(passgen_generator_set_len(generator, 20) == PASSGEN_STATUS_OK);
+    assert(passgen_generator_set_addition(generator, "\xcc\x81") == PASSGEN_STATUS_OK);
+    assert(passgen_generate(generator, buffer, sizeof(buffer), NULL) == PASSGEN_STATUS_INVALID_CHAR);
+    passgen_generator_free(generator);
+    passgen_generator_free(NULL);
+}
+
+int main(void) {
+    generate_test();
+    setter_test();
+    error_test();
+    puts("ok");
+    return 0;
+}
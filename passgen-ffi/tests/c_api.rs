@@ -0,0 +1,27 @@
+use std::{env, path::Path, process::Command};
+
+/// compile C test program with static library of this crate, then run it
+#[test]
+fn c_api_test() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // static library is built next to this test, in target/<profile>/deps
+    let deps_dir = env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let program = Path::new(env!("CARGO_TARGET_TMPDIR")).join("passgen_test");
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg("-std=c99")
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg(crate_dir.join("tests/c/passgen_test.c"))
+        .arg(deps_dir.join("libpassgen_ffi.a"))
+        .args(["-lpthread", "-ldl", "-lm"])
+        .arg("-o")
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let output = Command::new(&program).output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
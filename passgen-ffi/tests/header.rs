@@ -0,0 +1,15 @@
+use std::{fs, path::Path};
+
+/// generated header should be committed, run with `PASSGEN_UPDATE_HEADER=1` to update it
+#[test]
+fn header_test() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_root_or_default(crate_dir);
+    let mut generated = Vec::new();
+    cbindgen::generate_with_config(crate_dir, config).unwrap().write(&mut generated);
+    let path = Path::new(crate_dir).join("include/passgen.h");
+    if std::env::var_os("PASSGEN_UPDATE_HEADER").is_some() {
+        fs::write(&path, &generated).unwrap();
+    }
+    assert_eq!(String::from_utf8(generated).unwrap(), fs::read_to_string(path).unwrap());
+}
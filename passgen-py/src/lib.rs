@@ -0,0 +1,454 @@
+//! Python bindings of passgen, built on `PasswordGenerator`.
+
+use passgen_core::{
+    filter::WordFilter,
+    generator,
+    keyboard::{KeyboardLayout, KeyboardSetting, KEYBOARD_DEFAULT_MIN_ENTROPY},
+    password::{zero_string, LengthUnit, Password},
+    symbol::{self, MarkPreset, UnicodeClass},
+    GeneratorError, ParseError, PasswordError, PASSWORD_DEFAULT_LENGTH,
+};
+use pyo3::{
+    create_exception,
+    exceptions::{PyException, PyValueError},
+    prelude::*,
+};
+
+create_exception!(passgen, PassgenError, PyException, "base of errors raised by passgen");
+create_exception!(passgen, EmptyLengthError, PassgenError, "password length is 0");
+create_exception!(passgen, TooShortLengthError, PassgenError, "password is too short");
+create_exception!(passgen, TooLongLengthError, PassgenError, "password is too long");
+create_exception!(passgen, EmptySymbolError, PassgenError, "no available symbol");
+create_exception!(passgen, TooManyRedrawError, PassgenError, "too many redraws by filter");
+create_exception!(passgen, LowEntropyError, PassgenError, "password entropy is too low");
+create_exception!(passgen, TooFewSwitchesError, PassgenError, "max layer switches is too few");
+create_exception!(passgen, InvalidCharError, PassgenError, "character cannot be used");
+create_exception!(passgen, KeyboardWithBytesError, PassgenError, "keyboard with length in bytes");
+create_exception!(passgen, TooManyClassesError, PassgenError, "classes do not fit in length");
+create_exception!(passgen, PasswordTooLongError, PassgenError, "password does not fit in buffer");
+
+/// convert error of generator into python exception, such as `TooShortLengthError`
+fn to_py_err(err: anyhow::Error) -> PyErr {
+    let message = err.to_string();
+    match err.downcast_ref::<GeneratorError>() {
+        Some(GeneratorError::EmptyLength) => EmptyLengthError::new_err(message),
+        Some(GeneratorError::TooShortLength(_)) => TooShortLengthError::new_err(message),
+        Some(GeneratorError::TooLongLength(_)) => TooLongLengthError::new_err(message),
+        Some(GeneratorError::EmptySymbol) => EmptySymbolError::new_err(message),
+        Some(GeneratorError::TooManyRedraw(_)) => TooManyRedrawError::new_err(message),
+        Some(GeneratorError::LowEntropy(_, _)) => LowEntropyError::new_err(message),
+        Some(GeneratorError::TooFewSwitches(_, _)) => TooFewSwitchesError::new_err(message),
+        Some(GeneratorError::InvalidChar(_)) => InvalidCharError::new_err(message),
+        Some(GeneratorError::KeyboardWithBytes) => KeyboardWithBytesError::new_err(message),
+        Some(GeneratorError::TooManyClasses(_, _)) => TooManyClassesError::new_err(message),
+        None if err.is::<PasswordError>() => PasswordTooLongError::new_err(message),
+        None => PassgenError::new_err(message),
+    }
+}
+
+/// unknown name such as `"dvorak"` layout is `ValueError`
+fn parse_err(err: ParseError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// Generated password, which is hidden in `str` and `repr`, and can be wiped explicitly.
+///
+/// `reveal` returns python `str`, which cannot be wiped, so reveal it as late as possible.
+#[pyclass(module = "passgen")]
+pub struct Secret {
+    password: Option<Password>,
+}
+
+impl Secret {
+    fn password(&self) -> PyResult<&Password> {
+        self.password.as_ref().ok_or_else(|| PyValueError::new_err("secret is already wiped"))
+    }
+}
+
+#[pymethods]
+impl Secret {
+    /// return password as str
+    fn reveal(&self) -> PyResult<String> {
+        Ok(self.password()?.to_string())
+    }
+
+    /// zero password, then it cannot be revealed
+    fn wipe(&mut self) {
+        self.password = None; // password is zero-filled when dropped
+    }
+
+    #[getter]
+    fn wiped(&self) -> bool {
+        self.password.is_none()
+    }
+
+    fn __len__(&self) -> PyResult<usize> {
+        Ok(self.password()?.len())
+    }
+
+    fn __str__(&self) -> String {
+        "********".to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        match self.password {
+            Some(_) => "Secret(********)".to_string(),
+            None => "Secret(wiped)".to_string(),
+        }
+    }
+
+    fn __enter__(slf: Py<Self>) -> Py<Self> {
+        slf
+    }
+
+    fn __exit__(
+        &mut self,
+        _exc_type: Py<PyAny>,
+        _exc_value: Py<PyAny>,
+        _traceback: Py<PyAny>,
+    ) -> bool {
+        self.wipe();
+        false
+    }
+}
+
+/// Set of marks, which password uses.
+#[pyclass(module = "passgen", from_py_object)]
+#[derive(Clone)]
+pub struct MarkSet {
+    mark: symbol::MarkSet,
+}
+
+#[pymethods]
+impl MarkSet {
+    /// default marks if `marks` is None
+    #[new]
+    #[pyo3(signature = (marks=None))]
+    fn new(marks: Option<&str>) -> PyResult<Self> {
+        let mark = match marks {
+            Some(marks) => {
+                let mut mark = symbol::MarkSet::new();
+                for c in marks.chars() {
+                    if !mark.contains(&c) && !mark.insert(c) {
+                        Err(PyValueError::new_err(format!("{:?} cannot be used as mark", c)))?
+                    }
+                }
+                mark
+            }
+            None => symbol::MarkSet::default(),
+        };
+        Ok(Self { mark })
+    }
+
+    /// marks which are safe in context such as `"shell"`
+    #[staticmethod]
+    fn from_preset(preset: &str) -> PyResult<Self> {
+        let preset = preset.parse::<MarkPreset>().map_err(parse_err)?;
+        Ok(Self { mark: symbol::MarkSet::from_preset(preset) })
+    }
+
+    fn restrict(&mut self, preset: &str) -> PyResult<()> {
+        self.mark.restrict(preset.parse::<MarkPreset>().map_err(parse_err)?);
+        Ok(())
+    }
+
+    fn insert(&mut self, mark: char) -> bool {
+        self.mark.insert(mark)
+    }
+
+    fn insert_all(&mut self) {
+        self.mark.insert_all()
+    }
+
+    fn remove(&mut self, mark: char) -> bool {
+        self.mark.remove(&mark)
+    }
+
+    fn toggle(&mut self, mark: char) -> bool {
+        self.mark.toggle(mark)
+    }
+
+    fn clear(&mut self) {
+        self.mark.clear()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.mark.is_empty()
+    }
+
+    fn __contains__(&self, mark: char) -> bool {
+        self.mark.contains(&mark)
+    }
+
+    fn __len__(&self) -> usize {
+        self.mark.iter().count()
+    }
+
+    fn __str__(&self) -> String {
+        self.mark.iter().collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MarkSet({:?})", self.__str__())
+    }
+
+    fn __eq__(&self, other: PyRef<'_, Self>) -> bool {
+        self.mark.iter().eq(other.mark.iter())
+    }
+}
+
+/// Password generator, whose settings are same as passgen cli.
+#[pyclass(module = "passgen")]
+pub struct PasswordGenerator {
+    generator: generator::PasswordGenerator,
+}
+
+impl Drop for PasswordGenerator {
+    fn drop(&mut self) {
+        for context in self.generator.context.iter_mut() {
+            // context such as username is zeroed, same as password
+            zero_string(context);
+        }
+    }
+}
+
+#[pymethods]
+impl PasswordGenerator {
+    #[new]
+    #[pyo3(signature = (
+        len=PASSWORD_DEFAULT_LENGTH,
+        use_lower=true,
+        use_upper=true,
+        use_numeric=true,
+        mark=None,
+        addition="",
+        context=Vec::new(),
+        unicode=Vec::new(),
+        unit="chars",
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        len: usize,
+        use_lower: bool,
+        use_upper: bool,
+        use_numeric: bool,
+        mark: Option<MarkSet>,
+        addition: &str,
+        context: Vec<String>,
+        unicode: Vec<String>,
+        unit: &str,
+    ) -> PyResult<Self> {
+        let mut generator = Self { generator: generator::PasswordGenerator::default() };
+        generator.generator.len = len;
+        generator.generator.use_lower = use_lower;
+        generator.generator.use_upper = use_upper;
+        generator.generator.use_numeric = use_numeric;
+        generator.generator.mark = mark.map(|m| m.mark).unwrap_or_default();
+        generator.set_addition(addition);
+        generator.generator.context = context;
+        generator.set_unicode(unicode)?;
+        generator.set_unit(unit)?;
+        Ok(generator)
+    }
+
+    #[getter]
+    fn get_len(&self) -> usize {
+        self.generator.len
+    }
+
+    #[setter]
+    fn set_len(&mut self, len: usize) {
+        self.generator.len = len
+    }
+
+    #[getter]
+    fn get_use_lower(&self) -> bool {
+        self.generator.use_lower
+    }
+
+    #[setter]
+    fn set_use_lower(&mut self, use_lower: bool) {
+        self.generator.use_lower = use_lower
+    }
+
+    #[getter]
+    fn get_use_upper(&self) -> bool {
+        self.generator.use_upper
+    }
+
+    #[setter]
+    fn set_use_upper(&mut self, use_upper: bool) {
+        self.generator.use_upper = use_upper
+    }
+
+    #[getter]
+    fn get_use_numeric(&self) -> bool {
+        self.generator.use_numeric
+    }
+
+    #[setter]
+    fn set_use_numeric(&mut self, use_numeric: bool) {
+        self.generator.use_numeric = use_numeric
+    }
+
+    /// copy of marks, so set it again after modified
+    #[getter]
+    fn get_mark(&self) -> MarkSet {
+        MarkSet { mark: self.generator.mark.clone() }
+    }
+
+    #[setter]
+    fn set_mark(&mut self, mark: MarkSet) {
+        self.generator.mark = mark.mark
+    }
+
+    /// custom characters, which are NFC normalized
+    #[getter]
+    fn get_addition(&self) -> String {
+        self.generator.addition.iter().collect()
+    }
+
+    #[setter]
+    fn set_addition(&mut self, addition: &str) {
+        self.generator.addition = symbol::normalized_chars(addition)
+    }
+
+    /// strings such as username, whose tokens must not appear in password
+    #[getter]
+    fn get_context(&self) -> Vec<String> {
+        self.generator.context.clone()
+    }
+
+    #[setter]
+    fn set_context(&mut self, context: Vec<String>) {
+        for old in self.generator.context.iter_mut() {
+            zero_string(old);
+        }
+        self.generator.context = context
+    }
+
+    /// unicode classes such as `"greek"`, which password uses too
+    #[getter]
+    fn get_unicode(&self) -> Vec<&'static str> {
+        self.generator.unicode.iter().map(UnicodeClass::name).collect()
+    }
+
+    #[setter]
+    fn set_unicode(&mut self, unicode: Vec<String>) -> PyResult<()> {
+        self.generator.unicode = unicode
+            .iter()
+            .map(|class| class.parse::<UnicodeClass>())
+            .collect::<Result<_, _>>()
+            .map_err(parse_err)?;
+        Ok(())
+    }
+
+    /// unit of password length, `"chars"`, `"graphemes"` or `"bytes"`
+    #[getter]
+    fn get_unit(&self) -> &'static str {
+        self.generator.unit.name()
+    }
+
+    #[setter]
+    fn set_unit(&mut self, unit: &str) -> PyResult<()> {
+        self.generator.unit = unit.parse::<LengthUnit>().map_err(parse_err)?;
+        Ok(())
+    }
+
+    /// the number of words in word filter
+    #[getter]
+    fn get_filter_len(&self) -> usize {
+        self.generator.filter.len()
+    }
+
+    /// set word filter, with bundled word lists or not, and additional words
+    #[pyo3(signature = (words=Vec::new(), bundled=false))]
+    fn set_filter(&mut self, words: Vec<String>, bundled: bool) {
+        let mut filter = if bundled { WordFilter::bundled() } else { WordFilter::new() };
+        for word in words.iter() {
+            filter.insert(word);
+        }
+        self.generator.filter = filter;
+    }
+
+    /// keyboard setting as tuple of layout, max switches and min entropy, or None
+    #[getter]
+    fn get_keyboard(&self) -> Option<(&'static str, Option<usize>, f64)> {
+        let keyboard = self.generator.keyboard.as_ref()?;
+        Some((keyboard.layout.name(), keyboard.max_switches, keyboard.min_entropy))
+    }
+
+    /// set keyboard layout such as `"ios"`, or None to ignore layout.
+    /// if max switches is None, switches are minimized keeping min entropy
+    #[pyo3(signature = (layout, max_switches=None, min_entropy=KEYBOARD_DEFAULT_MIN_ENTROPY))]
+    fn set_keyboard(
+        &mut self,
+        layout: Option<&str>,
+        max_switches: Option<usize>,
+        min_entropy: f64,
+    ) -> PyResult<()> {
+        self.generator.keyboard = match layout {
+            Some(layout) => {
+                let layout = layout.parse::<KeyboardLayout>().map_err(parse_err)?;
+                Some(KeyboardSetting { layout, max_switches, min_entropy })
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// generate password, raise `PassgenError` if settings are invalid
+    fn generate_password(&self) -> PyResult<Secret> {
+        let password = self.generator.generate_password().map_err(to_py_err)?;
+        Ok(Secret { password: Some(password) })
+    }
+
+    /// raise `PassgenError` if password cannot be generated with current settings
+    fn can_generate(&self) -> PyResult<()> {
+        self.generator.can_generate().map_err(to_py_err)
+    }
+
+    /// return true if password satisfies settings, such as numeric is used when use_numeric
+    fn validate(&self, password: &Bound<'_, PyAny>) -> PyResult<bool> {
+        if let Ok(secret) = password.cast::<Secret>() {
+            Ok(self.generator.validate(secret.borrow().password()?))
+        } else {
+            let chars: Vec<char> = password.extract::<String>()?.chars().collect();
+            let password = Password::from_chars(&chars).map_err(to_py_err)?;
+            Ok(self.generator.validate(&password))
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PasswordGenerator(len={}, use_lower={}, use_upper={}, use_numeric={}, mark={})",
+            self.generator.len,
+            self.generator.use_lower,
+            self.generator.use_upper,
+            self.generator.use_numeric,
+            self.get_mark().__repr__(),
+        )
+    }
+}
+
+#[pymodule]
+#[pyo3(name = "passgen")]
+fn passgen_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PasswordGenerator>()?;
+    m.add_class::<MarkSet>()?;
+    m.add_class::<Secret>()?;
+    let py = m.py();
+    m.add("PassgenError", py.get_type::<PassgenError>())?;
+    m.add("EmptyLengthError", py.get_type::<EmptyLengthError>())?;
+    m.add("TooShortLengthError", py.get_type::<TooShortLengthError>())?;
+    m.add("TooLongLengthError", py.get_type::<TooLongLengthError>())?;
+    m.add("EmptySymbolError", py.get_type::<EmptySymbolError>())?;
+    m.add("TooManyRedrawError", py.get_type::<TooManyRedrawError>())?;
+    m.add("LowEntropyError", py.get_type::<LowEntropyError>())?;
+    m.add("TooFewSwitchesError", py.get_type::<TooFewSwitchesError>())?;
+    m.add("InvalidCharError", py.get_type::<InvalidCharError>())?;
+    m.add("KeyboardWithBytesError", py.get_type::<KeyboardWithBytesError>())?;
+    m.add("TooManyClassesError", py.get_type::<TooManyClassesError>())?;
+    m.add("PasswordTooLongError", py.get_type::<PasswordTooLongError>())?;
+    Ok(())
+}
@@ -0,0 +1,140 @@
+import string
+
+import pytest
+
+import passgen
+
+
+def test_generate_password():
+    generator = passgen.PasswordGenerator()
+    secret = generator.generate_password()
+    password = secret.reveal()
+    assert len(password) == len(secret) == 20
+    assert any(c in string.ascii_lowercase for c in password)
+    assert any(c in string.ascii_uppercase for c in password)
+    assert any(c in string.digits for c in password)
+    assert any(c in passgen.MarkSet() for c in password)
+    assert generator.validate(secret)
+    assert generator.validate(password)
+
+
+def test_secret_is_hidden_and_wiped():
+    secret = passgen.PasswordGenerator().generate_password()
+    assert str(secret) == "********"
+    assert repr(secret) == "Secret(********)"
+    assert not secret.wiped
+    secret.wipe()
+    assert secret.wiped
+    assert repr(secret) == "Secret(wiped)"
+    with pytest.raises(ValueError):
+        secret.reveal()
+
+
+def test_secret_is_wiped_by_with_statement():
+    with passgen.PasswordGenerator().generate_password() as secret:
+        assert len(secret.reveal()) == 20
+    assert secret.wiped
+
+
+def test_settings():
+    generator = passgen.PasswordGenerator(
+        len=8, use_upper=False, use_numeric=False, mark=passgen.MarkSet("")
+    )
+    assert (generator.len, generator.use_lower, generator.use_upper) == (8, True, False)
+    assert generator.mark.is_empty()
+    password = generator.generate_password().reveal()
+    assert len(password) == 8
+    assert all(c in string.ascii_lowercase for c in password)
+
+    generator.addition = "é"
+    assert generator.addition == "é"
+    generator.context = ["alice", "alice@example.com"]
+    assert generator.context == ["alice", "alice@example.com"]
+    generator.unicode = ["greek"]
+    assert generator.unicode == ["greek"]
+    generator.unit = "graphemes"
+    assert generator.unit == "graphemes"
+    generator.set_filter(["hunter"], bundled=True)
+    assert generator.filter_len > 1
+    password = generator.generate_password().reveal()
+    assert "alice" not in password
+    assert "é" in password
+
+
+def test_keyboard():
+    generator = passgen.PasswordGenerator()
+    assert generator.keyboard is None
+    generator.set_keyboard("ios", max_switches=4)
+    assert generator.keyboard == ("ios", 4, 64.0)
+    assert generator.validate(generator.generate_password())
+    generator.set_keyboard(None)
+    assert generator.keyboard is None
+    with pytest.raises(ValueError):
+        generator.set_keyboard("dvorak")
+
+
+def test_mark_set():
+    mark = passgen.MarkSet()
+    assert str(mark) == "!#&@^"
+    assert "!" in mark and "a" not in mark
+    assert mark.insert("?") and "?" in mark
+    assert not mark.insert("a")
+    assert mark.toggle("?") and "?" not in mark
+    mark.restrict("shell")
+    assert mark == passgen.MarkSet("@")
+    assert len(passgen.MarkSet.from_preset("url")) == 3
+    with pytest.raises(ValueError):
+        passgen.MarkSet("a")
+    with pytest.raises(ValueError):
+        passgen.MarkSet.from_preset("json")
+
+
+@pytest.mark.parametrize(
+    "settings, error",
+    [
+        (dict(len=0), passgen.EmptyLengthError),
+        (dict(len=7), passgen.TooShortLengthError),
+        (dict(len=129), passgen.TooLongLengthError),
+        (
+            dict(use_lower=False, use_upper=False, use_numeric=False, mark=passgen.MarkSet("")),
+            passgen.EmptySymbolError,
+        ),
+        (dict(addition="́"), passgen.InvalidCharError),
+    ],
+)
+def test_errors(settings, error):
+    generator = passgen.PasswordGenerator(**settings)
+    with pytest.raises(error):
+        generator.can_generate()
+    with pytest.raises(passgen.PassgenError):
+        generator.generate_password()
+
+
+def test_keyboard_errors():
+    generator = passgen.PasswordGenerator(unit="bytes")
+    generator.set_keyboard("us")
+    with pytest.raises(passgen.KeyboardWithBytesError):
+        generator.can_generate()
+    generator = passgen.PasswordGenerator()
+    generator.set_keyboard("ios", max_switches=0)
+    with pytest.raises(passgen.TooFewSwitchesError):
+        generator.can_generate()
+    generator.set_keyboard("ios", max_switches=2, min_entropy=1000.0)
+    with pytest.raises(passgen.LowEntropyError):
+        generator.can_generate()
+
+
+def test_too_many_redraw():
+    generator = passgen.PasswordGenerator(
+        use_lower=False, use_upper=False, use_numeric=False, mark=passgen.MarkSet(""), addition="a"
+    )
+    generator.set_filter(["aaa"])
+    with pytest.raises(passgen.TooManyRedrawError):
+        generator.generate_password()
+
+
+def test_unknown_name():
+    with pytest.raises(ValueError):
+        passgen.PasswordGenerator(unit="words")
+    with pytest.raises(ValueError):
+        passgen.PasswordGenerator(unicode=["hangul"])
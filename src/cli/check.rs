@@ -0,0 +1,76 @@
+use anyhow::Context;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use passgen_core::{
+    password::{fill_volatile, zero_string, Password},
+    PASSWORD_FILL_CHARACTER,
+};
+
+use super::{generate, Helps};
+
+/// `check` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    let check = SubCommand::with_name("check")
+        .about("check existing password against settings, exit with 1 if it fails")
+        .arg(Arg::with_name("password").help("password, read from stdin if omitted"))
+        .arg(
+            Arg::with_name("length")
+                .help("min password length(default: length of settings)")
+                .long("min-length")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("settings")
+                .help("read settings of password in JSON from this file, instead of saved settings")
+                .long("settings")
+                .takes_value(true)
+                .value_name("FILE"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .help("output format(default: text)")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "json"]),
+        );
+    generate::generator_args(check, helps)
+}
+
+/// check password by `check` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    match check(arg_matches) {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(error) => {
+            super::print_error(arg_matches, &error);
+            1
+        }
+    }
+}
+
+/// print failures of password, and return true if it passes
+fn check(arg_matches: &ArgMatches) -> anyhow::Result<bool> {
+    let settings = super::base_settings(arg_matches)?;
+    let generator = generate::generator_from_matches(arg_matches, settings)?;
+    let mut input = super::value_or_stdin(arg_matches, "password");
+    let mut chars: Vec<_> = input.chars().collect();
+    let password = Password::from_chars(&chars);
+    fill_volatile(&mut chars, PASSWORD_FILL_CHARACTER);
+    zero_string(&mut input);
+    let failures = generator.check(&password.context("password is too long to check")?);
+    match arg_matches.value_of("format").unwrap_or("text") {
+        "json" => {
+            let failures: Vec<_> = failures.iter().map(ToString::to_string).collect();
+            println!(
+                "{}",
+                serde_json::json!({ "passed": failures.is_empty(), "failures": failures })
+            );
+        }
+        _ if failures.is_empty() => println!("passed"),
+        _ => {
+            for failure in failures.iter() {
+                println!("{}", failure);
+            }
+        }
+    }
+    Ok(failures.is_empty())
+}
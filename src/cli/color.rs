@@ -0,0 +1,92 @@
+use passgen_core::generator::{CharClass, PasswordGenerator};
+use std::{ffi::OsString, io::IsTerminal};
+
+/// ANSI escape sequence which resets color
+pub const ANSI_RESET: &str = "\x1b[0m";
+
+/// return ANSI escape sequence of character class, close to colors of web
+pub fn ansi_color(class: CharClass) -> &'static str {
+    match class {
+        CharClass::Lower => "\x1b[37m",
+        CharClass::Upper => "\x1b[92m",
+        CharClass::Numeric => "\x1b[33m",
+        CharClass::Mark => "\x1b[36m",
+        CharClass::Addition => "\x1b[31m",
+    }
+}
+
+/// return true if output should be colored by `--color` choice, `NO_COLOR` and terminal
+pub fn enabled(choice: &str, no_color: Option<OsString>, terminal: bool) -> bool {
+    match choice {
+        "always" => true,
+        "never" => false,
+        // https://no-color.org/ disables color if it is present and not empty
+        _ => terminal && no_color.is_none_or(|no_color| no_color.is_empty()),
+    }
+}
+
+/// return true if stdout should be colored by `--color` choice
+pub fn stdout_enabled(choice: &str) -> bool {
+    enabled(choice, std::env::var_os("NO_COLOR"), std::io::stdout().is_terminal())
+}
+
+/// return password, each character is colored by its class
+pub fn colorize(generator: &PasswordGenerator, password: &str) -> String {
+    let len: usize =
+        password.chars().map(|c| ansi_color(generator.classify(&c)).len() + c.len_utf8()).sum();
+    let mut colored = String::with_capacity(len + ANSI_RESET.len());
+    for c in password.chars() {
+        colored.push_str(ansi_color(generator.classify(&c)));
+        colored.push(c);
+    }
+    colored.push_str(ANSI_RESET);
+    colored
+}
+
+/// return legend of classes used by generator, each name is colored by the class if `color`
+pub fn legend(generator: &PasswordGenerator, color: bool) -> String {
+    let used = [
+        (CharClass::Lower, generator.use_lower),
+        (CharClass::Upper, generator.use_upper),
+        (CharClass::Numeric, generator.use_numeric),
+        (CharClass::Mark, !generator.mark.is_empty()),
+        (CharClass::Addition, !generator.addition.is_empty() || !generator.unicode.is_empty()),
+    ];
+    let names: Vec<_> = used
+        .iter()
+        .filter(|(_, used)| *used)
+        .map(|(class, _)| match color {
+            true => format!("{}{}{}", ansi_color(*class), class.name(), ANSI_RESET),
+            false => class.name().to_string(),
+        })
+        .collect();
+    names.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn enabled_test() {
+        assert!(enabled("auto", None, true));
+        assert!(enabled("auto", Some(OsString::new()), true));
+        assert!(!enabled("auto", Some(OsString::from("1")), true));
+        assert!(!enabled("auto", None, false));
+        assert!(enabled("always", Some(OsString::from("1")), false));
+        assert!(!enabled("never", None, true));
+    }
+
+    #[test]
+    fn colorize_test() {
+        let mut generator = PasswordGenerator::default();
+        assert_eq!(colorize(&generator, "l1|"), "\x1b[37ml\x1b[33m1\x1b[31m|\x1b[0m");
+        assert_eq!(legend(&generator, false), "lower upper numeric mark");
+        generator.use_upper = false;
+        generator.addition = vec!['|'].into_iter().collect();
+        assert_eq!(
+            legend(&generator, true),
+            "\x1b[37mlower\x1b[0m \x1b[33mnumeric\x1b[0m \x1b[36mmark\x1b[0m \x1b[31maddition\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use anyhow::Context;
+use clap::{App, AppSettings, ArgMatches, SubCommand};
+use passgen_core::generator::PasswordGenerator;
+use std::path::{Path, PathBuf};
+
+use super::{generate, Helps};
+
+/// environment variable of settings file path
+pub const CONFIG_FILE_ENV: &str = "PASSGEN_CONFIG";
+
+/// `config` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    let set = SubCommand::with_name("set")
+        .about("save settings of password, which are used by generate and check by default")
+        .arg(generate::length_arg(helps));
+    SubCommand::with_name("config")
+        .about("show or change saved settings of password")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .subcommand(SubCommand::with_name("path").about(&helps.config_file[..]))
+        .subcommand(SubCommand::with_name("show").about("print saved settings in JSON"))
+        .subcommand(generate::generator_args(set, helps))
+        .subcommand(SubCommand::with_name("reset").about("remove saved settings"))
+}
+
+/// show or change saved settings by `config` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    let (command, arg_matches) = match arg_matches.subcommand() {
+        (command, Some(arg_matches)) => (command, arg_matches),
+        _ => unreachable!("subcommand is required by clap"),
+    };
+    let path = config_path();
+    let result = match command {
+        "path" => {
+            println!("{}", path.display());
+            Ok(())
+        }
+        "show" => load(&path).and_then(|settings| {
+            println!("{}", serde_json::to_string_pretty(&settings)?);
+            Ok(())
+        }),
+        "set" => load(&path)
+            .and_then(|settings| generate::generator_from_matches(arg_matches, settings))
+            .and_then(|settings| {
+                settings.can_generate()?;
+                save(&path, &settings)
+            }),
+        "reset" => match std::fs::remove_file(&path) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => {
+                Err(error).with_context(|| format!("cannot remove {}", path.display()))
+            }
+            _ => Ok(()),
+        },
+        _ => unreachable!("possible subcommands are validated by clap"),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            1
+        }
+    }
+}
+
+/// return path of settings file given by environment variable, or default
+pub fn config_path() -> PathBuf {
+    if let Some(file) = std::env::var_os(CONFIG_FILE_ENV) {
+        return PathBuf::from(file);
+    }
+    let config_home = std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from).unwrap_or_else(|| {
+        let home = std::env::var_os("HOME").unwrap_or_default();
+        PathBuf::from(home).join(".config")
+    });
+    config_home.join("passgen").join("settings.json")
+}
+
+/// load saved settings, or default settings if it is not saved
+pub fn load(path: &Path) -> anyhow::Result<PasswordGenerator> {
+    if !path.exists() {
+        return Ok(PasswordGenerator::default());
+    }
+    super::read_settings(&path.to_string_lossy())
+        .with_context(|| format!("invalid settings file {}, fix or reset it", path.display()))
+}
+
+/// save settings in JSON, creating its directory
+fn save(path: &Path, settings: &PasswordGenerator) -> anyhow::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(settings)? + "\n")
+        .with_context(|| format!("cannot write settings file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// run `config set` with arguments on settings file of path
+    fn set(path: &Path, args: &[&str]) -> anyhow::Result<()> {
+        let helps = Helps::default();
+        let args = ["passgen", "config", "set"].iter().chain(args.iter());
+        let arg_matches = super::super::app(&helps).get_matches_from_safe(args)?;
+        let arg_matches = arg_matches.subcommand_matches("config").unwrap();
+        let arg_matches = arg_matches.subcommand_matches("set").unwrap();
+        let settings = generate::generator_from_matches(arg_matches, load(path)?)?;
+        settings.can_generate()?;
+        save(path, &settings)
+    }
+
+    #[test]
+    fn set_round_trip_test() {
+        let dir = std::env::temp_dir().join(format!("passgen-config-test-{}", std::process::id()));
+        let path = dir.join("passgen").join("settings.json");
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(load(&path).unwrap().len, PasswordGenerator::default().len);
+
+        set(&path, &["12", "-n", "--preset", "shell", "-c", "alice"]).unwrap();
+        let settings = load(&path).unwrap();
+        assert_eq!((settings.len, settings.use_numeric), (12, false));
+        assert_eq!(settings.context, ["alice"]);
+        let marks: String = settings.mark.iter().collect();
+
+        // later set overrides only given settings
+        set(&path, &["--numeric"]).unwrap();
+        let settings = load(&path).unwrap();
+        assert_eq!((settings.len, settings.use_numeric), (12, true));
+        assert_eq!(settings.mark.iter().collect::<String>(), marks);
+
+        // invalid settings are not saved
+        assert!(set(&path, &["2"]).is_err());
+        assert!(set(&path, &["--lower", "--no-lower"]).is_err());
+        assert_eq!(load(&path).unwrap().len, 12);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
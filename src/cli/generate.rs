@@ -0,0 +1,583 @@
+use anyhow::Context;
+use clap::{App, Arg, ArgGroup, ArgMatches, SubCommand};
+use passgen_core::{
+    escape::Escape,
+    filter::BUNDLED_WORDS,
+    generator::{CharClass, PasswordGenerator},
+    hash::HashScheme,
+    keyboard::KeyboardSetting,
+    otp,
+    password::zero_string,
+    phonetic::{Reading, PHONETIC_DEFAULT_CHUNK},
+    qr::{Qr, WifiNetwork},
+    symbol::{self, UnicodeClass},
+    PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH,
+};
+
+use super::{color, osc52, output, Helps};
+use std::path::Path;
+
+/// `generate` subcommand, whose arguments are same as top level
+pub fn command<'a, 'b>() -> App<'a, 'b> {
+    SubCommand::with_name("generate")
+        .about("generate password, which is default command if subcommand is omitted")
+}
+
+/// positional password length
+pub fn length_arg(helps: &Helps) -> Arg<'_, '_> {
+    Arg::with_name("length").help(&helps.length[..])
+}
+
+/// add length, settings of password generator and output arguments
+pub fn args<'a, 'b>(app: App<'a, 'b>, helps: &'a Helps) -> App<'a, 'b> {
+    generator_args(app.arg(length_arg(helps)), helps)
+        .arg(
+            Arg::with_name("escape")
+                .help("quote or escape password for this context")
+                .short("e")
+                .long("escape")
+                .takes_value(true)
+                .possible_values(&helps.escapes),
+        )
+        .arg(
+            Arg::with_name("settings")
+                .help("read settings of password in JSON from this file, or stdin if it is `-`, instead of saved settings, options override them")
+                .long("settings")
+                .takes_value(true)
+                .value_name("FILE"),
+        )
+        .arg(
+            Arg::with_name("format")
+                .help("output format, json and ndjson include length, classes, entropy bits, settings and timestamp(default: text)")
+                .long("format")
+                .takes_value(true)
+                .possible_values(&["text", "json", "ndjson"]),
+        )
+        .arg(
+            Arg::with_name("count")
+                .help("the number of passwords, which are printed as an array in json format")
+                .long("count")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .help("report how many times password is redrawn by word filter or context")
+                .short("v")
+                .long("verbose"),
+        )
+        .arg(
+            Arg::with_name("hash")
+                .help("print hash of password with random salt, for /etc/shadow, htpasswd or database")
+                .long("hash")
+                .takes_value(true)
+                .value_name("SCHEME")
+                .case_insensitive(true)
+                .possible_values(&helps.schemes),
+        )
+        .arg(
+            Arg::with_name("hash_only")
+                .help("print hash only, instead of password and hash")
+                .long("hash-only")
+                .requires("hash")
+                .conflicts_with_all(&["escape", "qr", "wifi"]),
+        )
+        .arg(
+            Arg::with_name("qr")
+                .help("print QR code of password with unicode half blocks, to scan it by phone")
+                .long("qr"),
+        )
+        .arg(
+            Arg::with_name("wifi")
+                .help("print QR code to join Wi-Fi network of this ssid with password")
+                .long("wifi")
+                .takes_value(true)
+                .value_name("SSID"),
+        )
+        .arg(
+            Arg::with_name("wifi_security")
+                .help("authentication type of Wi-Fi network(default: WPA)")
+                .long("wifi-security")
+                .takes_value(true)
+                .case_insensitive(true)
+                .possible_values(&helps.securities)
+                .requires("wifi"),
+        )
+        .arg(
+            Arg::with_name("clip")
+                .help("copy password to clipboard of terminal through OSC 52, instead of printing it")
+                .long("clip")
+                .conflicts_with_all(&["format", "count", "qr", "wifi", "hash_only"]),
+        )
+        .arg(
+            Arg::with_name("clip_clear")
+                .help("clear clipboard after this seconds, waiting for it")
+                .long("clip-clear")
+                .takes_value(true)
+                .value_name("SECONDS")
+                .requires("clip"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .help("write password into this file atomically, instead of printing it, or into this directory with --secret")
+                .short("o")
+                .long("output")
+                .takes_value(true)
+                .value_name("PATH")
+                .conflicts_with_all(&["format", "count", "qr", "wifi", "clip"]),
+        )
+        .arg(
+            Arg::with_name("secret")
+                .help("write password of this name into output directory, as systemd credentials or docker secrets, repeat for more")
+                .long("secret")
+                .takes_value(true)
+                .value_name("NAME")
+                .multiple(true)
+                .number_of_values(1)
+                .requires("output"),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .help("permission of written file in octal(default: 600)")
+                .long("mode")
+                .takes_value(true)
+                .requires("output"),
+        )
+        .arg(
+            Arg::with_name("force")
+                .help("overwrite existing output file")
+                .long("force")
+                .requires("output"),
+        )
+        .arg(
+            Arg::with_name("no_newline")
+                .help("do not print or write newline after password")
+                .long("no-newline"),
+        )
+        .arg(
+            Arg::with_name("phonetic")
+                .help("print how to read password aloud in chunks of 4 characters, such as \"1-4 aB3^: alfa, capital Bravo, three, caret\"")
+                .long("phonetic")
+                .conflicts_with_all(&["format", "clip", "output", "hash_only"]),
+        )
+        .arg(
+            Arg::with_name("reading")
+                .help("words of --phonetic, NATO/ICAO spelling alphabet or Japanese kana(default: nato)")
+                .long("reading")
+                .takes_value(true)
+                .case_insensitive(true)
+                .possible_values(&helps.readings)
+                .requires("phonetic"),
+        )
+        .arg(
+            Arg::with_name("color")
+                .help("color each character by its class, auto is disabled by NO_COLOR or if stdout is not terminal(default: auto)")
+                .long("color")
+                .takes_value(true)
+                .possible_values(&["auto", "always", "never"]),
+        )
+        .arg(
+            Arg::with_name("legend")
+                .help("print classes of characters with their colors to stderr")
+                .long("legend"),
+        )
+        .group(ArgGroup::with_name("qr_code").args(&["qr", "wifi"]))
+}
+
+/// add settings of password generator except length, shared by `generate`, `check`,
+/// `config set` and `vault add`. each class has a pair of flags, and only one of them is allowed
+pub fn generator_args<'a, 'b>(app: App<'a, 'b>, helps: &'a Helps) -> App<'a, 'b> {
+    app.arg(Arg::with_name("lower").help("use lower character").long("lower"))
+        .arg(Arg::with_name("no_lower").help("no lower character").short("l").long("no-lower"))
+        .arg(Arg::with_name("upper").help("use upper character").long("upper"))
+        .arg(Arg::with_name("no_upper").help("no upper character").short("u").long("no-upper"))
+        .arg(Arg::with_name("numeric").help("use numeric").long("numeric"))
+        .arg(Arg::with_name("no_numeric").help("no numeric").short("n").long("no-numeric"))
+        .arg(Arg::with_name("mark").help("use default mark").long("mark"))
+        .arg(Arg::with_name("no_mark").help("no default mark").short("m").long("no-mark"))
+        .arg(
+            Arg::with_name("addition")
+                .help("custom addition")
+                .short("a")
+                .long("addition")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("unicode")
+                .help("use this unicode class too")
+                .short("U")
+                .long("unicode")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .possible_values(&helps.classes),
+        )
+        .arg(
+            Arg::with_name("unit")
+                .help("unit of password length(default: chars)")
+                .long("unit")
+                .takes_value(true)
+                .possible_values(&helps.units),
+        )
+        .arg(
+            Arg::with_name("preset")
+                .help("use only marks which are safe in this context, instead of default mark")
+                .short("p")
+                .long("preset")
+                .takes_value(true)
+                .possible_values(&helps.presets),
+        )
+        .arg(
+            Arg::with_name("layout")
+                .help("minimize layer switches such as shift on this keyboard layout")
+                .short("k")
+                .long("layout")
+                .takes_value(true)
+                .possible_values(&helps.layouts),
+        )
+        .arg(
+            Arg::with_name("max_switches")
+                .help("cap layer switches on the keyboard layout, instead of minimizing")
+                .long("max-switches")
+                .takes_value(true)
+                .requires("layout"),
+        )
+        .arg(
+            Arg::with_name("min_entropy")
+                .help(&helps.min_entropy[..])
+                .long("min-entropy")
+                .takes_value(true)
+                .requires("layout"),
+        )
+        .arg(
+            Arg::with_name("filter")
+                .help("redraw password containing profanity of bundled word lists")
+                .short("f")
+                .long("filter"),
+        )
+        .arg(
+            Arg::with_name("no_filter")
+                .help("no word filter, even if it is saved in settings")
+                .long("no-filter"),
+        )
+        .arg(
+            Arg::with_name("words")
+                .help("redraw password containing words of this file, one word per line")
+                .short("w")
+                .long("words")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("context")
+                .help("redraw password containing 3+ character token of this, such as username")
+                .short("c")
+                .long("context")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .group(ArgGroup::with_name("lowers").args(&["lower", "no_lower"]))
+        .group(ArgGroup::with_name("uppers").args(&["upper", "no_upper"]))
+        .group(ArgGroup::with_name("numerics").args(&["numeric", "no_numeric"]))
+        .group(ArgGroup::with_name("marks").args(&["mark", "no_mark", "preset"]))
+        .group(ArgGroup::with_name("filters").args(&["filter", "no_filter"]))
+}
+
+/// generate passwords and print them, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    match generate(arg_matches) {
+        Ok(()) => 0,
+        Err(error) => {
+            super::print_error(arg_matches, &error);
+            1
+        }
+    }
+}
+
+/// generate passwords by settings given by command line, and print them in the format
+fn generate(arg_matches: &ArgMatches) -> anyhow::Result<()> {
+    let generator = generator_from_matches(arg_matches, super::base_settings(arg_matches)?)?;
+    let untypeable: String = generator.get_untypeable_chars().into_iter().collect();
+    if let (Some(keyboard), false) = (&generator.keyboard, untypeable.is_empty()) {
+        eprintln!(
+            "warning: {:?} cannot be typed with {} keyboard layout",
+            untypeable,
+            keyboard.layout.name()
+        );
+    }
+    let names: Vec<_> = arg_matches.values_of("secret").map(Iterator::collect).unwrap_or_default();
+    let count = match arg_matches.value_of("count") {
+        Some(count) => count.parse().context("count must be positive integer")?,
+        None => names.len().max(1),
+    };
+    let mode = match arg_matches.value_of("mode") {
+        Some(mode) => output::parse_mode(mode)?,
+        None => output::OUTPUT_DEFAULT_MODE,
+    };
+    let newline = if arg_matches.is_present("no_newline") { "" } else { "\n" };
+    let format = arg_matches.value_of("format").unwrap_or("text");
+    let entropy = generator.entropy()?;
+    let clip_clear = match arg_matches.value_of("clip_clear") {
+        Some(seconds) => Some(seconds.parse().context("clip clear must be non negative integer")?),
+        None => None,
+    };
+    let reading: Reading = match arg_matches.value_of("reading") {
+        Some(reading) => reading.parse()?,
+        None => Reading::default(),
+    };
+    let color = color::stdout_enabled(arg_matches.value_of("color").unwrap_or("auto"));
+    let (mut records, mut clip, mut outputs) = (Vec::new(), None, Vec::new());
+    for _ in 0..count {
+        let (password, redraw) = generator.generate_password_with_redraw()?;
+        if arg_matches.is_present("verbose") {
+            eprintln!(
+                "redrawn {} times by word filter of {} words and {} context tokens",
+                redraw,
+                generator.filter.len(),
+                generator.get_context_tokens().len(),
+            );
+        }
+        let mut text = password.to_string();
+        let qr = qr_code(arg_matches, &text)?;
+        let hash = password_hash(arg_matches, &text)?;
+        let mut escaped = arg_matches.value_of("escape").map(|escape| {
+            let escape: Escape = escape.parse().expect("possible values are validated by clap");
+            escape.escape(&text)
+        });
+        if format != "text" {
+            let mut record = serde_json::json!({
+                "password": &text,
+                "length": generator.unit.measure(&password),
+                "unit": generator.unit.name(),
+                "classes": class_names(&generator),
+                "entropy_bits": entropy,
+                "settings": &generator,
+                "timestamp": otp::unix_time(),
+            });
+            if let (true, Some(record)) =
+                (arg_matches.is_present("hash_only"), record.as_object_mut())
+            {
+                record.remove("password");
+            }
+            for (key, value) in
+                [("escaped", escaped), ("hash", hash), ("qr_svg", qr.map(|qr| qr.to_svg()))]
+            {
+                if let Some(value) = value {
+                    record[key] = value.into();
+                }
+            }
+            match format {
+                "ndjson" => {
+                    println!("{}", record);
+                    zero_record(&mut record);
+                }
+                _ => records.push(record),
+            }
+            zero_string(&mut text);
+            continue;
+        }
+        match escaped.as_deref() {
+            _ if arg_matches.is_present("hash_only") => (),
+            secret if arg_matches.is_present("clip") => {
+                clip = Some(secret.unwrap_or(&text).to_string())
+            }
+            secret if arg_matches.is_present("output") => {
+                outputs.push([secret.unwrap_or(&text), newline].concat())
+            }
+            Some(escaped) => print!("{}{}", escaped, newline),
+            None if color => {
+                let mut colored = color::colorize(&generator, &text);
+                print!("{}{}", colored, newline);
+                zero_string(&mut colored);
+            }
+            None => print!("{:?}{}", password, newline),
+        }
+        if let Some(escaped) = escaped.as_mut() {
+            zero_string(escaped);
+        }
+        match hash {
+            Some(hash)
+                if arg_matches.is_present("hash_only") && arg_matches.is_present("output") =>
+            {
+                outputs.push(hash + newline)
+            }
+            Some(hash) => print!("{}{}", hash, newline),
+            None => (),
+        }
+        if let Some(qr) = qr {
+            print!("{}", qr.to_half_blocks());
+        }
+        if arg_matches.is_present("phonetic") {
+            for mut chunk in reading.chunks(&text, PHONETIC_DEFAULT_CHUNK) {
+                println!("{}", chunk);
+                for text in chunk.words.iter_mut().chain([&mut chunk.text]) {
+                    zero_string(text);
+                }
+            }
+        }
+        zero_string(&mut text);
+    }
+    if arg_matches.is_present("legend") {
+        eprintln!("{}", color::legend(&generator, color));
+    }
+    if let Some(mut text) = clip {
+        let result = osc52::copy(&text, clip_clear);
+        zero_string(&mut text);
+        result?;
+    }
+    if let Some(path) = arg_matches.value_of("output") {
+        let force = arg_matches.is_present("force");
+        let result = match names.is_empty() {
+            true => output::write_secret(Path::new(path), &outputs[0], mode, force),
+            false => {
+                let secrets: Vec<_> =
+                    names.iter().copied().zip(outputs.iter().map(|s| &s[..])).collect();
+                output::write_secrets(Path::new(path), &secrets, mode, force)
+            }
+        };
+        for mut text in outputs {
+            zero_string(&mut text);
+        }
+        result?;
+    }
+    if format == "json" {
+        // written to stdout directly, not through string which would be left unzeroed
+        let result = match arg_matches.is_present("count") {
+            true => serde_json::to_writer_pretty(std::io::stdout(), &records),
+            false => serde_json::to_writer_pretty(std::io::stdout(), &records[0]),
+        };
+        for record in records.iter_mut() {
+            zero_record(record);
+        }
+        result?;
+        println!();
+    }
+    Ok(())
+}
+
+/// zero-fill password and escaped password in JSON record
+fn zero_record(record: &mut serde_json::Value) {
+    for key in ["password", "escaped"] {
+        if let Some(serde_json::Value::String(secret)) = record.get_mut(key) {
+            zero_string(secret);
+        }
+    }
+}
+
+/// return names of character classes which are used in every password, such as "lower"
+fn class_names(generator: &PasswordGenerator) -> Vec<&'static str> {
+    let mut classes = Vec::new();
+    for (class, used) in [
+        (CharClass::Lower, generator.use_lower),
+        (CharClass::Upper, generator.use_upper),
+        (CharClass::Numeric, generator.use_numeric),
+        (CharClass::Mark, !generator.mark.is_empty()),
+        (CharClass::Addition, !generator.addition.is_empty()),
+    ] {
+        if used {
+            classes.push(class.name());
+        }
+    }
+    classes.extend(generator.unicode.iter().map(UnicodeClass::name));
+    classes
+}
+
+/// return password generator of settings, overridden by command line.
+/// settings which are not given by command line are kept as they are
+pub fn generator_from_matches(
+    arg_matches: &ArgMatches,
+    mut generator: PasswordGenerator,
+) -> anyhow::Result<PasswordGenerator> {
+    for (used, name, no_name) in [
+        (&mut generator.use_lower, "lower", "no_lower"),
+        (&mut generator.use_upper, "upper", "no_upper"),
+        (&mut generator.use_numeric, "numeric", "no_numeric"),
+    ] {
+        if arg_matches.is_present(name) {
+            *used = true;
+        } else if arg_matches.is_present(no_name) {
+            *used = false;
+        }
+    }
+    if let Some(preset) = arg_matches.value_of("preset") {
+        let preset = preset.parse().expect("possible values are validated by clap");
+        generator.mark = symbol::MarkSet::from_preset(preset);
+    }
+    if arg_matches.is_present("mark") {
+        generator.mark = symbol::MarkSet::default();
+    } else if arg_matches.is_present("no_mark") {
+        generator.mark.clear();
+    }
+    if let Some(add) = arg_matches.value_of("addition") {
+        generator.addition = symbol::normalized_chars(add);
+    }
+    if let Some(classes) = arg_matches.values_of("unicode") {
+        generator.unicode = classes
+            .map(|class| class.parse().expect("possible values are validated by clap"))
+            .collect();
+    }
+    if let Some(unit) = arg_matches.value_of("unit") {
+        generator.unit = unit.parse().expect("possible values are validated by clap");
+    }
+    if arg_matches.is_present("filter") {
+        for (_, words) in BUNDLED_WORDS.iter() {
+            generator.filter.extend_from_text(words);
+        }
+    } else if arg_matches.is_present("no_filter") {
+        generator.filter.clear();
+    }
+    if let Some(path) = arg_matches.value_of("words") {
+        let words = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read word file {}", path))?;
+        generator.filter.extend_from_text(&words);
+    }
+    if let Some(context) = arg_matches.values_of("context") {
+        generator.context = context.map(String::from).collect();
+    }
+    if let Some(layout) = arg_matches.value_of("layout") {
+        let mut keyboard =
+            KeyboardSetting::new(layout.parse().expect("possible values are validated by clap"));
+        if let Some(max_switches) = arg_matches.value_of("max_switches") {
+            keyboard.max_switches =
+                Some(max_switches.parse().context("max switches must be non negative integer")?);
+        }
+        if let Some(min_entropy) = arg_matches.value_of("min_entropy") {
+            keyboard.min_entropy = min_entropy.parse().context("min entropy must be number")?;
+        }
+        generator.keyboard = Some(keyboard);
+    }
+    if let Some(len) = arg_matches.value_of("length") {
+        generator.len = len.parse().with_context(|| {
+            format!(
+                "length must be integer, that is in [{}, {}]",
+                PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH
+            )
+        })?;
+    }
+    Ok(generator)
+}
+
+/// return hash of password, if it is required by command line
+fn password_hash(arg_matches: &ArgMatches, password: &str) -> anyhow::Result<Option<String>> {
+    match arg_matches.value_of("hash") {
+        Some(scheme) => {
+            let scheme: HashScheme = scheme.parse().expect("possible values are validated by clap");
+            Ok(Some(scheme.hash(password)?))
+        }
+        None => Ok(None),
+    }
+}
+
+/// return QR code of password or Wi-Fi credential, if it is required by command line
+fn qr_code(arg_matches: &ArgMatches, password: &str) -> anyhow::Result<Option<Qr>> {
+    match arg_matches.value_of("wifi") {
+        Some(ssid) => {
+            let mut network = WifiNetwork::new(ssid);
+            if let Some(security) = arg_matches.value_of("wifi_security") {
+                network.security = security.parse().expect("possible values are validated by clap");
+            }
+            Ok(Some(Qr::new(&network.payload(password)?.to_string())?))
+        }
+        None if arg_matches.is_present("qr") => Ok(Some(Qr::new(password)?)),
+        None => Ok(None),
+    }
+}
@@ -0,0 +1,342 @@
+pub mod check;
+pub mod color;
+pub mod config;
+pub mod generate;
+pub mod osc52;
+pub mod otp;
+pub mod output;
+pub mod phrase;
+pub mod pin;
+pub mod token;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod vault;
+
+use anyhow::Context;
+use clap::{App, AppSettings, ArgMatches};
+use passgen_core::{
+    api_token::{ApiTokenSpec, Checksum, API_TOKEN_DEFAULT_BODY_LENGTH},
+    escape::Escape,
+    generator::PasswordGenerator,
+    hash::HashScheme,
+    keyboard::{KeyboardLayout, KEYBOARD_DEFAULT_MIN_ENTROPY},
+    otp::{OtpAlgorithm, OTP_DEFAULT_SECRET_BYTES},
+    password::LengthUnit,
+    phonetic::Reading,
+    phrase::{PHRASE_DEFAULT_SEPARATOR, PHRASE_DEFAULT_WORDS},
+    pin::PIN_DEFAULT_LENGTH,
+    qr::WifiSecurity,
+    symbol::{MarkPreset, MarkSet, UnicodeClass},
+    token::{Encoding, TOKEN_DEFAULT_BYTES},
+    HashError, WifiError, PASSWORD_DEFAULT_LENGTH,
+};
+use std::{
+    ffi::OsString,
+    io::Read,
+    num::{ParseFloatError, ParseIntError},
+};
+
+/// help messages and possible values, which are formatted once and borrowed by arguments
+pub struct Helps {
+    pub about: String,
+    pub length: String,
+    pub bytes: String,
+    pub encoding: String,
+    pub body_length: String,
+    pub alphabet: String,
+    pub checksum: String,
+    pub secret_bytes: String,
+    pub min_entropy: String,
+    pub vault_file: String,
+    pub config_file: String,
+    pub words: String,
+    pub separator: String,
+    pub pin_length: String,
+    pub presets: Vec<&'static str>,
+    pub escapes: Vec<&'static str>,
+    pub schemes: Vec<&'static str>,
+    pub securities: Vec<&'static str>,
+    pub layouts: Vec<&'static str>,
+    pub classes: Vec<&'static str>,
+    pub units: Vec<&'static str>,
+    pub encodings: Vec<&'static str>,
+    pub checksums: Vec<&'static str>,
+    pub algorithms: Vec<&'static str>,
+    pub readings: Vec<&'static str>,
+}
+
+impl Default for Helps {
+    fn default() -> Self {
+        Self {
+            about: format!(
+                "This command line tool generate secure random password. \n\
+                By default, password consists of lower alphabet and upper alphabet \n\
+                and numeric and marks such as \"{}\".",
+                MarkSet::DEFAULT_MARK
+            ),
+            length: format!("password length(default: {})", PASSWORD_DEFAULT_LENGTH),
+            bytes: format!("the number of random bytes(default: {})", TOKEN_DEFAULT_BYTES),
+            encoding: format!("encoding of random bytes(default: {})", Encoding::default().name()),
+            body_length: format!(
+                "length of random body(default: {})",
+                API_TOKEN_DEFAULT_BODY_LENGTH
+            ),
+            alphabet: format!(
+                "alphabet of random body(default: {})",
+                ApiTokenSpec::default().alphabet.name()
+            ),
+            checksum: format!(
+                "checksum suffix(default: {})",
+                ApiTokenSpec::default().checksum.name()
+            ),
+            secret_bytes: format!(
+                "the number of random bytes of secret(default: {})",
+                OTP_DEFAULT_SECRET_BYTES
+            ),
+            min_entropy: format!(
+                "min entropy bits of password with keyboard layout(default: {})",
+                KEYBOARD_DEFAULT_MIN_ENTROPY
+            ),
+            vault_file: format!(
+                "vault file(default: ${} or ~/.local/share/passgen/vault)",
+                vault::VAULT_FILE_ENV
+            ),
+            config_file: format!(
+                "settings file(default: ${} or ~/.config/passgen/settings.json)",
+                config::CONFIG_FILE_ENV
+            ),
+            words: format!("the number of words(default: {})", PHRASE_DEFAULT_WORDS),
+            separator: format!(
+                "separator of words, a character or empty(default: {})",
+                PHRASE_DEFAULT_SEPARATOR
+            ),
+            pin_length: format!("the number of digits(default: {})", PIN_DEFAULT_LENGTH),
+            presets: MarkPreset::ALL.iter().map(MarkPreset::name).collect(),
+            escapes: Escape::ALL.iter().map(Escape::name).collect(),
+            schemes: HashScheme::ALL.iter().map(HashScheme::name).collect(),
+            securities: WifiSecurity::ALL.iter().map(WifiSecurity::name).collect(),
+            layouts: KeyboardLayout::ALL.iter().map(KeyboardLayout::name).collect(),
+            classes: UnicodeClass::ALL.iter().map(UnicodeClass::name).collect(),
+            units: LengthUnit::ALL.iter().map(LengthUnit::name).collect(),
+            encodings: Encoding::ALL.iter().map(Encoding::name).collect(),
+            checksums: Checksum::ALL.iter().map(Checksum::name).collect(),
+            algorithms: OtpAlgorithm::ALL.iter().map(OtpAlgorithm::name).collect(),
+            readings: Reading::ALL.iter().map(Reading::name).collect(),
+        }
+    }
+}
+
+/// return command line app. without subcommand, top level arguments run `generate`
+pub fn app(helps: &Helps) -> App<'_, '_> {
+    let app = App::new("passgen")
+        .author(clap::crate_authors!())
+        .version(clap::crate_version!())
+        .about(&helps.about[..])
+        .setting(AppSettings::ArgsNegateSubcommands)
+        .setting(AppSettings::VersionlessSubcommands);
+    let app = generate::args(app, helps)
+        .subcommand(generate::args(generate::command(), helps))
+        .subcommand(check::command(helps))
+        .subcommand(phrase::command(helps))
+        .subcommand(pin::command(helps))
+        .subcommand(token::command(helps))
+        .subcommand(token::api_token_command(helps))
+        .subcommand(otp::command(helps))
+        .subcommand(vault::command(helps))
+        .subcommand(config::command(helps));
+    #[cfg(feature = "tui")]
+    let app = app.subcommand(tui::command(helps));
+    app
+}
+
+/// parse command line, or print its error and return exit code.
+/// error is printed as JSON if `--format` in command line is JSON, although it is not parsed yet
+pub fn get_matches<'a>(helps: &'a Helps, args: &[OsString]) -> Result<ArgMatches<'a>, i32> {
+    let format = args.iter().enumerate().find_map(|(i, arg)| match arg.to_str()? {
+        "--format" => args.get(i + 1)?.to_str(),
+        arg => arg.strip_prefix("--format="),
+    });
+    match format {
+        Some(format @ ("json" | "ndjson")) => {
+            let app = app(helps).setting(AppSettings::ColorNever);
+            app.get_matches_from_safe(args).map_err(|error| match error.use_stderr() {
+                true => {
+                    print_error_as(format, &error.into());
+                    1
+                }
+                false => error.exit(),
+            })
+        }
+        _ => Ok(app(helps).get_matches_from(args)),
+    }
+}
+
+/// run subcommand, or `generate` if it is omitted, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    match arg_matches.subcommand() {
+        ("generate", Some(arg_matches)) => generate::run(arg_matches),
+        ("check", Some(arg_matches)) => check::run(arg_matches),
+        ("phrase", Some(arg_matches)) => phrase::run(arg_matches),
+        ("pin", Some(arg_matches)) => pin::run(arg_matches),
+        ("token", Some(arg_matches)) => token::run(arg_matches),
+        ("api-token", Some(arg_matches)) => token::run_api_token(arg_matches),
+        ("otp", Some(arg_matches)) => otp::run(arg_matches),
+        ("vault", Some(arg_matches)) => vault::run(arg_matches),
+        ("config", Some(arg_matches)) => config::run(arg_matches),
+        #[cfg(feature = "tui")]
+        ("tui", Some(arg_matches)) => tui::run(arg_matches),
+        _ => generate::run(arg_matches),
+    }
+}
+
+/// read generator settings in JSON from file, or from stdin if path is `-`
+pub fn read_settings(path: &str) -> anyhow::Result<PasswordGenerator> {
+    let json = match path {
+        "-" => {
+            let mut json = String::new();
+            std::io::stdin()
+                .read_to_string(&mut json)
+                .context("cannot read settings from stdin")?;
+            json
+        }
+        path => std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read settings file {}", path))?,
+    };
+    serde_json::from_str(&json).context("invalid settings JSON")
+}
+
+/// return settings given by `--settings`, or saved by `config set`, or default
+pub fn base_settings(arg_matches: &ArgMatches) -> anyhow::Result<PasswordGenerator> {
+    match arg_matches.value_of("settings") {
+        Some(path) => read_settings(path),
+        None => config::load(&config::config_path()),
+    }
+}
+
+/// print error, as JSON object of its kind and message if the format is JSON
+pub fn print_error(arg_matches: &ArgMatches, error: &anyhow::Error) {
+    print_error_as(arg_matches.value_of("format").unwrap_or("text"), error)
+}
+
+/// print error in the format, which is text, json or ndjson
+fn print_error_as(format: &str, error: &anyhow::Error) {
+    match format {
+        "text" => eprintln!("{:?}", error),
+        _ => {
+            let kind = match passgen_core::error_kind(error) {
+                "Unknown" if error.is::<serde_json::Error>() => "InvalidSettings",
+                "Unknown" if error.is::<std::io::Error>() => "Io",
+                "Unknown" if error.is::<ParseIntError>() || error.is::<ParseFloatError>() => {
+                    "InvalidArgument"
+                }
+                "Unknown" if error.is::<WifiError>() => "InvalidWifi",
+                "Unknown" if error.is::<HashError>() => "InvalidHash",
+                "Unknown" if error.is::<clap::Error>() => "InvalidArgument",
+                kind => kind,
+            };
+            let message = match error.downcast_ref::<clap::Error>() {
+                // usage and hint following the first line are for terminal
+                Some(error) => {
+                    let line = error.message.lines().next().unwrap_or_default();
+                    line.trim_start_matches("error: ").to_string()
+                }
+                None => format!("{:#}", error),
+            };
+            eprintln!("{}", serde_json::json!({ "error": { "kind": kind, "message": message } }));
+        }
+    }
+}
+
+/// return value of the argument, or read a line from stdin to keep secret out of process lists
+pub fn value_or_stdin(arg_matches: &ArgMatches, name: &str) -> String {
+    match arg_matches.value_of(name) {
+        Some(value) => value.to_string(),
+        None => {
+            let mut line = String::new();
+            std::io::stdin()
+                .read_line(&mut line)
+                .unwrap_or_else(|e| panic!("cannot read {} from stdin: {}", name, e));
+            line.trim_end_matches(&['\r', '\n'][..]).to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use passgen_core::symbol::MarkPreset;
+
+    fn parse(args: &[&str]) -> clap::Result<ArgMatches<'static>> {
+        let helps = Box::leak(Box::new(Helps::default()));
+        app(helps).get_matches_from_safe(std::iter::once("passgen").chain(args.iter().copied()))
+    }
+
+    fn settings(args: &[&str]) -> PasswordGenerator {
+        let arg_matches = parse(args).unwrap();
+        let arg_matches = arg_matches.subcommand().1.unwrap_or(&arg_matches);
+        generate::generator_from_matches(arg_matches, PasswordGenerator::default()).unwrap()
+    }
+
+    #[test]
+    fn default_subcommand_test() {
+        let arg_matches = parse(&["16"]).unwrap();
+        assert_eq!(arg_matches.subcommand_name(), None);
+        assert_eq!(arg_matches.value_of("length"), Some("16"));
+        let arg_matches = parse(&["generate", "16"]).unwrap();
+        assert_eq!(arg_matches.subcommand_name(), Some("generate"));
+        assert_eq!(settings(&["generate", "16"]).len, 16);
+        assert_eq!(parse(&["pin", "8"]).unwrap().subcommand_name(), Some("pin"));
+        assert_eq!(parse(&[]).unwrap().subcommand_name(), None);
+        assert!(parse(&["unknown-subcommand"]).is_ok_and(|m| m.subcommand_name().is_none()));
+    }
+
+    #[test]
+    fn class_conflict_test() {
+        for (on, off) in [("--lower", "--no-lower"), ("--upper", "-u"), ("--numeric", "-n")] {
+            assert!(parse(&[on, off]).is_err());
+            assert!(parse(&["generate", on, off]).is_err());
+            assert!(parse(&["check", on, off, "p@ss"]).is_err());
+        }
+        assert!(parse(&["--filter", "--no-filter"]).is_err());
+        let generator = settings(&["--lower", "--no-upper"]);
+        assert!(generator.use_lower && !generator.use_upper && generator.use_numeric);
+    }
+
+    #[test]
+    fn marks_group_test() {
+        assert!(parse(&["--preset", "shell", "--mark"]).is_err());
+        assert!(parse(&["--preset", "shell", "-m"]).is_err());
+        assert!(parse(&["--mark", "--no-mark"]).is_err());
+        assert!(parse(&["--preset", "sql"]).is_err());
+        let marks = |mark: &MarkSet| mark.iter().collect::<String>();
+        let generator = settings(&["--preset", "shell"]);
+        assert_eq!(marks(&generator.mark), marks(&MarkSet::from_preset(MarkPreset::ShellSafe)));
+        assert!(settings(&["--no-mark"]).mark.is_empty());
+        assert_eq!(marks(&settings(&["--mark"]).mark), marks(&MarkSet::default()));
+    }
+
+    #[test]
+    fn legacy_short_flags_test() {
+        for args in [&["-l", "-u", "-n", "-m", "-a", "xyz", "8"][..], &["-lunm", "-a", "xyz", "8"]]
+        {
+            let generator = settings(args);
+            assert!(!generator.use_lower && !generator.use_upper && !generator.use_numeric);
+            assert!(generator.mark.is_empty());
+            assert_eq!(generator.addition.iter().collect::<String>(), "xyz");
+            assert_eq!(generator.len, 8);
+        }
+        assert!(parse(&["-p", "url", "-f", "-c", "alice", "-c", "example.com"]).is_ok());
+        assert_eq!(settings(&["-c", "alice", "-c", "example.com"]).context.len(), 2);
+    }
+
+    #[test]
+    fn json_parse_error_test() {
+        let helps = Helps::default();
+        let args = |args: &[&str]| -> Vec<OsString> {
+            std::iter::once("passgen").chain(args.iter().copied()).map(Into::into).collect()
+        };
+        assert!(get_matches(&helps, &args(&["--format", "json", "--count", "2"])).is_ok());
+        assert_eq!(get_matches(&helps, &args(&["--format", "json", "--bogus"])).err(), Some(1));
+        assert_eq!(get_matches(&helps, &args(&["check", "--format=json", "-L"])).err(), Some(1));
+    }
+}
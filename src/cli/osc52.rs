@@ -0,0 +1,185 @@
+use passgen_core::{
+    password::{fill_volatile, zero_string},
+    token::Encoding,
+    PASSWORD_FILL_CHARACTER,
+};
+use std::{
+    io::{IsTerminal, Write},
+    time::Duration,
+};
+
+/// OSC 52 whose data is not base64 makes xterm clear its clipboard
+pub const OSC52_CLEAR: &str = "\x1b]52;c;!\x07";
+
+/// screen truncates long DCS string, so the sequence is split into chunks of this bytes
+pub const OSC52_SCREEN_CHUNK: usize = 76;
+
+/// terminal multiplexer, which passes OSC 52 through to outer terminal only if it is wrapped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    None,
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    /// detect multiplexer from `TMUX` and `TERM` environment variables
+    pub fn detect() -> Self {
+        let (tmux, term) = (std::env::var("TMUX").ok(), std::env::var("TERM").ok());
+        Self::from_env(tmux.as_deref(), term.as_deref())
+    }
+
+    /// return multiplexer of given `TMUX` and `TERM`
+    pub fn from_env(tmux: Option<&str>, term: Option<&str>) -> Self {
+        match (tmux, term) {
+            (Some(tmux), _) if !tmux.is_empty() => Multiplexer::Tmux,
+            (_, Some(term)) if term.starts_with("screen") => Multiplexer::Screen,
+            _ => Multiplexer::None,
+        }
+    }
+
+    /// wrap sequence in DCS passthrough of multiplexer
+    pub fn wrap(&self, sequence: &str) -> String {
+        match self {
+            Multiplexer::None => sequence.to_string(),
+            // tmux needs ESC in passthrough to be doubled
+            Multiplexer::Tmux => {
+                let mut wrapped = String::with_capacity(sequence.len() * 2 + 9);
+                wrapped.push_str("\x1bPtmux;");
+                for c in sequence.chars() {
+                    if c == '\x1b' {
+                        wrapped.push(c);
+                    }
+                    wrapped.push(c);
+                }
+                wrapped.push_str("\x1b\\");
+                wrapped
+            }
+            Multiplexer::Screen => {
+                let chunks = sequence.as_bytes().chunks(OSC52_SCREEN_CHUNK);
+                let mut wrapped = String::with_capacity(sequence.len() + chunks.len() * 4);
+                for chunk in chunks {
+                    wrapped.push_str("\x1bP");
+                    wrapped.push_str(&String::from_utf8_lossy(chunk));
+                    wrapped.push_str("\x1b\\");
+                }
+                wrapped
+            }
+        }
+    }
+}
+
+/// return OSC 52 sequence, which makes terminal write text into its clipboard
+pub fn copy_sequence(text: &str) -> String {
+    let mut encoded = Encoding::Base64.encode(text.as_bytes(), true);
+    let (prefix, suffix) = ("\x1b]52;c;", '\x07');
+    let mut sequence = String::with_capacity(prefix.len() + encoded.len() + 1);
+    sequence.push_str(prefix);
+    sequence.extend(encoded.iter());
+    sequence.push(suffix);
+    fill_volatile(&mut encoded, PASSWORD_FILL_CHARACTER);
+    sequence
+}
+
+/// copy text into clipboard of terminal, and clear it after given seconds if it is some.
+/// this refuses to write the sequence into pipe or file, which would leak text as it is
+pub fn copy(text: &str, clear_seconds: Option<u64>) -> anyhow::Result<()> {
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        anyhow::bail!("clipboard is written through terminal, but stdout is not terminal");
+    }
+    match clear_seconds {
+        Some(seconds) => {
+            eprintln!("copy to clipboard, which will be cleared in {} seconds", seconds)
+        }
+        None => eprintln!("copy to clipboard"),
+    }
+    let clear = clear_seconds.map(Duration::from_secs);
+    write_copy(&mut stdout, text, Multiplexer::detect(), clear)?;
+    Ok(())
+}
+
+/// write OSC 52 sequence of text wrapped for multiplexer, and clearing sequence after delay
+pub fn write_copy<W: Write>(
+    terminal: &mut W,
+    text: &str,
+    multiplexer: Multiplexer,
+    clear: Option<Duration>,
+) -> std::io::Result<()> {
+    let mut sequence = copy_sequence(text);
+    let mut wrapped = multiplexer.wrap(&sequence);
+    let result = terminal.write_all(wrapped.as_bytes()).and_then(|()| terminal.flush());
+    zero_string(&mut sequence);
+    zero_string(&mut wrapped);
+    result?;
+    if let Some(clear) = clear {
+        std::thread::sleep(clear);
+        terminal.write_all(multiplexer.wrap(OSC52_CLEAR).as_bytes())?;
+        terminal.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copy_sequence_test() {
+        assert_eq!(copy_sequence("pass"), "\x1b]52;c;cGFzcw==\x07");
+        assert_eq!(copy_sequence("p@ss!"), "\x1b]52;c;cEBzcyE=\x07");
+        assert_eq!(copy_sequence("パス"), "\x1b]52;c;44OR44K5\x07");
+        assert_eq!(OSC52_CLEAR.as_bytes(), b"\x1b]52;c;!\x07");
+    }
+
+    #[test]
+    fn multiplexer_test() {
+        assert_eq!(Multiplexer::from_env(None, Some("xterm-256color")), Multiplexer::None);
+        assert_eq!(Multiplexer::from_env(Some(""), Some("xterm")), Multiplexer::None);
+        let tmux = Multiplexer::from_env(Some("/tmp/tmux-1000/default,123,0"), Some("screen"));
+        assert_eq!(tmux, Multiplexer::Tmux);
+        assert_eq!(Multiplexer::from_env(None, Some("screen.xterm-256color")), Multiplexer::Screen);
+
+        let sequence = copy_sequence("pass");
+        assert_eq!(Multiplexer::None.wrap(&sequence).as_bytes(), b"\x1b]52;c;cGFzcw==\x07");
+        assert_eq!(
+            Multiplexer::Tmux.wrap(&sequence).as_bytes(),
+            b"\x1bPtmux;\x1b\x1b]52;c;cGFzcw==\x07\x1b\\"
+        );
+        assert_eq!(
+            Multiplexer::Screen.wrap(&sequence).as_bytes(),
+            b"\x1bP\x1b]52;c;cGFzcw==\x07\x1b\\"
+        );
+        assert_eq!(
+            Multiplexer::Tmux.wrap(OSC52_CLEAR).as_bytes(),
+            b"\x1bPtmux;\x1b\x1b]52;c;!\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn write_copy_test() {
+        let mut terminal = Vec::new();
+        write_copy(&mut terminal, "pass", Multiplexer::None, None).unwrap();
+        assert_eq!(terminal, b"\x1b]52;c;cGFzcw==\x07");
+
+        let mut terminal = Vec::new();
+        write_copy(&mut terminal, "pass", Multiplexer::Tmux, Some(Duration::from_millis(1)))
+            .unwrap();
+        assert_eq!(
+            terminal,
+            &b"\x1bPtmux;\x1b\x1b]52;c;cGFzcw==\x07\x1b\\\x1bPtmux;\x1b\x1b]52;c;!\x07\x1b\\"[..]
+        );
+    }
+
+    #[test]
+    fn screen_chunk_test() {
+        let sequence = copy_sequence(&"a".repeat(96)); // 7 + 128 + 1 bytes
+        let wrapped = Multiplexer::Screen.wrap(&sequence);
+        let chunks: Vec<_> = wrapped.split("\x1b\\").filter(|chunk| !chunk.is_empty()).collect();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|chunk| chunk.starts_with("\x1bP")));
+        assert_eq!(chunks[0].len(), 2 + OSC52_SCREEN_CHUNK);
+        let joined: String = chunks.iter().map(|chunk| &chunk[2..]).collect();
+        assert_eq!(joined, sequence);
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::Context;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use passgen_core::otp::{self, Otp, OtpKind, OTP_DEFAULT_SECRET_BYTES};
+
+use super::Helps;
+
+/// `otp` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    SubCommand::with_name("otp")
+        .about("generate TOTP/HOTP secret with otpauth URI, or compute its codes")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("algorithm")
+                .help("hash algorithm of HMAC(default: SHA1)")
+                .short("a")
+                .long("algorithm")
+                .takes_value(true)
+                .case_insensitive(true)
+                .possible_values(&helps.algorithms)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("digits")
+                .help("digits of code, from 6 to 8(default: 6)")
+                .short("d")
+                .long("digits")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("period")
+                .help("time step seconds of TOTP(default: 30)")
+                .short("p")
+                .long("period")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("counter")
+                .help("counter of HOTP, TOTP is used if omitted")
+                .short("c")
+                .long("counter")
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(
+            SubCommand::with_name("new")
+                .about("generate secret, print it in base32 and otpauth URI")
+                .arg(
+                    Arg::with_name("bytes")
+                        .help(&helps.secret_bytes[..])
+                        .short("b")
+                        .long("bytes")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("issuer")
+                        .help("issuer such as service name")
+                        .short("i")
+                        .long("issuer")
+                        .takes_value(true),
+                )
+                .arg(
+                    Arg::with_name("account")
+                        .help("account name such as email")
+                        .long("account")
+                        .takes_value(true)
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("code")
+                .about("print current and next code of base32 secret")
+                .arg(Arg::with_name("secret").help("base32 secret, read from stdin if omitted")),
+        )
+}
+
+/// generate otp secret or compute its codes by `otp` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    let (command, arg_matches) = match arg_matches.subcommand() {
+        (command, Some(arg_matches)) => (command, arg_matches),
+        _ => unreachable!("subcommand is required by clap"),
+    };
+    let result = match command {
+        "new" => secret_bytes(arg_matches).and_then(Otp::generate).and_then(|otp| {
+            let otp = otp_parameters(otp, arg_matches)?;
+            let issuer = arg_matches.value_of("issuer").unwrap_or("");
+            let account = arg_matches.value_of("account").expect("account is required by clap");
+            println!("{:?}", otp.secret_base32()?);
+            println!("{:?}", otp.uri(otp_kind(arg_matches)?, issuer, account)?);
+            Ok(())
+        }),
+        "code" => {
+            let secret = super::value_or_stdin(arg_matches, "secret");
+            Otp::from_base32(&secret).and_then(|otp| {
+                let otp = otp_parameters(otp, arg_matches)?;
+                match otp_kind(arg_matches)? {
+                    // look ahead is skipped if the next counter or time overflows
+                    OtpKind::Hotp(counter) => {
+                        println!("{}", otp.hotp(counter));
+                        if let Some(next) = counter.checked_add(1) {
+                            println!("{}", otp.hotp(next));
+                        }
+                    }
+                    OtpKind::Totp => {
+                        let now = otp::unix_time();
+                        println!("{}", otp.totp(now));
+                        if let Some(next) = now.checked_add(otp.period()) {
+                            println!("{}", otp.totp(next));
+                        }
+                        eprintln!("next code in {} seconds", otp.remaining(now));
+                    }
+                }
+                Ok(())
+            })
+        }
+        _ => unreachable!("possible subcommands are validated by clap"),
+    };
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            1
+        }
+    }
+}
+
+/// set algorithm, digits and period of otp given by command line
+fn otp_parameters(mut otp: Otp, arg_matches: &ArgMatches) -> anyhow::Result<Otp> {
+    if let Some(algorithm) = arg_matches.value_of("algorithm") {
+        otp.algorithm = algorithm.parse()?;
+    }
+    if let Some(digits) = arg_matches.value_of("digits") {
+        otp.set_digits(digits.parse().context("digits must be integer")?)?;
+    }
+    if let Some(period) = arg_matches.value_of("period") {
+        otp.set_period(period.parse().context("period must be positive integer")?)?;
+    }
+    Ok(otp)
+}
+
+/// HOTP if counter is given by command line, otherwise TOTP
+fn otp_kind(arg_matches: &ArgMatches) -> anyhow::Result<OtpKind> {
+    match arg_matches.value_of("counter") {
+        Some(counter) => {
+            Ok(OtpKind::Hotp(counter.parse().context("counter must be non negative integer")?))
+        }
+        None => Ok(OtpKind::Totp),
+    }
+}
+
+/// bytes of secret given by command line, or default
+fn secret_bytes(arg_matches: &ArgMatches) -> anyhow::Result<usize> {
+    match arg_matches.value_of("bytes") {
+        Some(bytes) => bytes.parse().context("bytes must be positive integer"),
+        None => Ok(OTP_DEFAULT_SECRET_BYTES),
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Context;
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+/// default permission of written secrets, readable and writable only by owner
+pub const OUTPUT_DEFAULT_MODE: u32 = 0o600;
+
+/// permission of directory created for named secrets, such as /run/secrets
+pub const OUTPUT_DIR_MODE: u32 = 0o700;
+
+/// parse octal permission such as `600` or `0o640`
+pub fn parse_mode(mode: &str) -> anyhow::Result<u32> {
+    let digits = mode.trim_start_matches("0o");
+    let mode = u32::from_str_radix(digits, 8).context("mode must be octal such as 600")?;
+    if mode > 0o777 {
+        anyhow::bail!("mode must be permission bits up to 777");
+    }
+    Ok(mode)
+}
+
+/// return error if name cannot be a file name of systemd credential or docker secret
+pub fn check_name(name: &str) -> anyhow::Result<()> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(&['/', '\\', '\0'][..]) {
+        anyhow::bail!("invalid secret name {:?}, it must be a file name without slash", name);
+    }
+    Ok(())
+}
+
+/// write secret into file atomically with given permission. the file is written as temporary
+/// file and then moved, so readers never see partial secret, and existing file is kept if not force
+pub fn write_secret(path: &Path, secret: &str, mode: u32, force: bool) -> anyhow::Result<()> {
+    let file_name = path
+        .file_name()
+        .with_context(|| format!("invalid output path {}", path.display()))?
+        .to_string_lossy();
+    let temporary = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+    let result = write_temporary(&temporary, secret, mode).and_then(|()| match force {
+        true => fs::rename(&temporary, path),
+        // hard link fails if path exists, which is atomic unlike checking existence before
+        false => fs::hard_link(&temporary, path),
+    });
+    let _ = fs::remove_file(&temporary);
+    match result {
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            anyhow::bail!("{} already exists, pass --force to overwrite it", path.display())
+        }
+        result => result.with_context(|| format!("cannot write secret to {}", path.display())),
+    }
+}
+
+/// write named secrets into directory, one file per name as systemd credentials or docker secrets.
+/// nothing is written if some of them already exist and not force
+pub fn write_secrets(
+    dir: &Path,
+    secrets: &[(&str, &str)],
+    mode: u32,
+    force: bool,
+) -> anyhow::Result<()> {
+    for (name, _) in secrets {
+        check_name(name)?;
+        if !force && dir.join(name).exists() {
+            anyhow::bail!(
+                "{} already exists, pass --force to overwrite it",
+                dir.join(name).display()
+            );
+        }
+    }
+    if !dir.exists() {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, OUTPUT_DIR_MODE);
+        builder
+            .create(dir)
+            .with_context(|| format!("cannot create directory {}", dir.display()))?;
+    }
+    for (name, secret) in secrets {
+        write_secret(&dir.join(name), secret, mode, force)?;
+    }
+    Ok(())
+}
+
+/// create new temporary file with permission and write secret, which is flushed to disk
+fn write_temporary(temporary: &Path, secret: &str, mode: u32) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, mode);
+    #[cfg(not(unix))]
+    let _ = mode;
+    let mut file = options.open(temporary)?;
+    // mode of open is masked by umask, so permission is set again explicitly
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(mode))?;
+    file.write_all(secret.as_bytes())?;
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "passgen-output-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parse_mode_test() {
+        assert_eq!(parse_mode("600").unwrap(), 0o600);
+        assert_eq!(parse_mode("0640").unwrap(), 0o640);
+        assert_eq!(parse_mode("0o400").unwrap(), 0o400);
+        assert!(parse_mode("800").is_err());
+        assert!(parse_mode("1777").is_err());
+        assert!(parse_mode("rw").is_err());
+    }
+
+    #[test]
+    fn write_secret_test() {
+        let dir = test_dir("file");
+        let path = dir.join("secret.txt");
+        write_secret(&path, "p@ss", OUTPUT_DEFAULT_MODE, false).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "p@ss");
+        let error = write_secret(&path, "other", OUTPUT_DEFAULT_MODE, false).unwrap_err();
+        assert!(error.to_string().contains("--force"));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "p@ss");
+        write_secret(&path, "other\n", 0o640, true).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "other\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o640);
+        }
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn write_secrets_test() {
+        let dir = test_dir("dir").join("secrets");
+        let secrets = [("db_password", "p@ss"), ("api_key", "key")];
+        write_secrets(&dir, &secrets, OUTPUT_DEFAULT_MODE, false).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("db_password")).unwrap(), "p@ss");
+        assert_eq!(fs::read_to_string(dir.join("api_key")).unwrap(), "key");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(fs::metadata(&dir).unwrap().permissions().mode() & 0o777, 0o700);
+            let mode = fs::metadata(dir.join("api_key")).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let secrets = [("new", "new"), ("api_key", "other")];
+        assert!(write_secrets(&dir, &secrets, OUTPUT_DEFAULT_MODE, false).is_err());
+        assert!(!dir.join("new").exists());
+        write_secrets(&dir, &secrets, OUTPUT_DEFAULT_MODE, true).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("api_key")).unwrap(), "other");
+
+        for name in ["", ".", "..", "a/b"] {
+            let secrets = [(name, "p@ss")];
+            assert!(write_secrets(&dir, &secrets, OUTPUT_DEFAULT_MODE, true).is_err());
+        }
+        fs::remove_dir_all(dir.parent().unwrap()).unwrap();
+    }
+}
@@ -0,0 +1,71 @@
+use anyhow::Context;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use passgen_core::phrase::PhraseGenerator;
+
+use super::Helps;
+
+/// `phrase` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    SubCommand::with_name("phrase")
+        .about("generate passphrase of words from EFF large wordlist, such as cradle-unmade-zigzag")
+        .arg(
+            Arg::with_name("words")
+                .help(&helps.words[..])
+                .short("w")
+                .long("words")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("separator")
+                .help(&helps.separator[..])
+                .short("s")
+                .long("separator")
+                .takes_value(true)
+                .empty_values(true),
+        )
+        .arg(
+            Arg::with_name("capitalize")
+                .help("capitalize the first letter of each word")
+                .short("C")
+                .long("capitalize"),
+        )
+        .arg(
+            Arg::with_name("verbose")
+                .help("report entropy bits of passphrase")
+                .short("v")
+                .long("verbose"),
+        )
+}
+
+/// generate passphrase by `phrase` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    let generated = phrase_generator(arg_matches)
+        .and_then(|generator| Ok((generator.generate_phrase()?, generator)));
+    match generated {
+        Ok((phrase, generator)) => {
+            if arg_matches.is_present("verbose") {
+                eprintln!("entropy of passphrase is {:.1} bits", generator.entropy());
+            }
+            println!("{:?}", phrase);
+            0
+        }
+        Err(error) => {
+            super::print_error(arg_matches, &error);
+            1
+        }
+    }
+}
+
+/// build phrase generator from command line
+fn phrase_generator(arg_matches: &ArgMatches) -> anyhow::Result<PhraseGenerator> {
+    let default = PhraseGenerator::default();
+    let words = match arg_matches.value_of("words") {
+        Some(words) => words.parse().context("words must be positive integer")?,
+        None => default.words,
+    };
+    let separator = match arg_matches.value_of("separator") {
+        Some(separator) => separator.to_string(),
+        None => default.separator,
+    };
+    Ok(PhraseGenerator { words, separator, capitalize: arg_matches.is_present("capitalize") })
+}
@@ -0,0 +1,41 @@
+use anyhow::Context;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use passgen_core::pin::PinGenerator;
+
+use super::Helps;
+
+/// `pin` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    SubCommand::with_name("pin")
+        .about("generate numeric PIN, which is not well known pattern such as 123456")
+        .arg(Arg::with_name("length").help(&helps.pin_length[..]))
+        .arg(
+            Arg::with_name("allow_weak")
+                .help("allow well known patterns such as 000000, 123456 or 121212")
+                .long("allow-weak"),
+        )
+}
+
+/// generate PIN by `pin` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    match pin_generator(arg_matches).and_then(|generator| generator.generate_pin()) {
+        Ok(pin) => {
+            println!("{:?}", pin);
+            0
+        }
+        Err(error) => {
+            super::print_error(arg_matches, &error);
+            1
+        }
+    }
+}
+
+/// build PIN generator from command line
+fn pin_generator(arg_matches: &ArgMatches) -> anyhow::Result<PinGenerator> {
+    let default = PinGenerator::default();
+    let len = match arg_matches.value_of("length") {
+        Some(len) => len.parse().context("length must be positive integer")?,
+        None => default.len,
+    };
+    Ok(PinGenerator { len, allow_weak: arg_matches.is_present("allow_weak") })
+}
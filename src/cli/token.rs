@@ -0,0 +1,161 @@
+use anyhow::Context;
+use clap::{App, AppSettings, Arg, ArgGroup, ArgMatches, SubCommand};
+use passgen_core::{
+    api_token::ApiTokenSpec,
+    token::{TokenGenerator, TokenSize},
+};
+
+use super::Helps;
+
+/// `token` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    SubCommand::with_name("token")
+        .about("generate encoded random token, such as API key or session secret")
+        .arg(
+            Arg::with_name("bytes")
+                .help(&helps.bytes[..])
+                .short("b")
+                .long("bytes")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("bits")
+                .help("entropy bits, each character is drawn from alphabet of encoding")
+                .long("bits")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("encoding")
+                .help(&helps.encoding[..])
+                .short("e")
+                .long("encoding")
+                .takes_value(true)
+                .possible_values(&helps.encodings),
+        )
+        .arg(
+            Arg::with_name("padding")
+                .help("pad base32 and base64 with \"=\", as RFC 4648")
+                .short("p")
+                .long("padding"),
+        )
+        .group(ArgGroup::with_name("size").args(&["bytes", "bits"]))
+}
+
+/// `api-token` subcommand
+pub fn api_token_command(helps: &Helps) -> App<'_, '_> {
+    SubCommand::with_name("api-token")
+        .about("generate or verify api token with prefix and checksum, such as acme_live_...")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("prefix")
+                .help("identifiable prefix of token, such as acme_live_")
+                .short("p")
+                .long("prefix")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("body_length")
+                .help(&helps.body_length[..])
+                .short("l")
+                .long("length")
+                .takes_value(true)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("alphabet")
+                .help(&helps.alphabet[..])
+                .short("a")
+                .long("alphabet")
+                .takes_value(true)
+                .possible_values(&helps.encodings)
+                .global(true),
+        )
+        .arg(
+            Arg::with_name("checksum")
+                .help(&helps.checksum[..])
+                .short("c")
+                .long("checksum")
+                .takes_value(true)
+                .possible_values(&helps.checksums)
+                .global(true),
+        )
+        .subcommand(SubCommand::with_name("new").about("generate api token"))
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("verify format and checksum of api token, offline")
+                .arg(Arg::with_name("token").help("api token, read from stdin if omitted")),
+        )
+}
+
+/// generate token by `token` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    match token_generator(arg_matches).and_then(|generator| generator.generate_token()) {
+        Ok(token) => {
+            println!("{:?}", token);
+            0
+        }
+        Err(error) => {
+            super::print_error(arg_matches, &error);
+            1
+        }
+    }
+}
+
+/// build token generator from command line
+fn token_generator(arg_matches: &ArgMatches) -> anyhow::Result<TokenGenerator> {
+    let default = TokenGenerator::default();
+    let size = match (arg_matches.value_of("bytes"), arg_matches.value_of("bits")) {
+        (Some(bytes), _) => {
+            TokenSize::Bytes(bytes.parse().context("bytes must be positive integer")?)
+        }
+        (_, Some(bits)) => TokenSize::Bits(bits.parse().context("bits must be positive integer")?),
+        _ => default.size,
+    };
+    let encoding = match arg_matches.value_of("encoding") {
+        Some(encoding) => encoding.parse().expect("possible values are validated by clap"),
+        None => default.encoding,
+    };
+    Ok(TokenGenerator::new(size, encoding, arg_matches.is_present("padding")))
+}
+
+/// generate or verify api token by `api-token` subcommand, and return exit code
+pub fn run_api_token(arg_matches: &ArgMatches) -> i32 {
+    let (command, arg_matches) = match arg_matches.subcommand() {
+        (command, Some(arg_matches)) => (command, arg_matches),
+        _ => unreachable!("subcommand is required by clap"),
+    };
+    let result = api_token_spec(arg_matches).and_then(|spec| match command {
+        "new" => spec.generate_token().map(|token| println!("{:?}", token)),
+        "verify" => {
+            let token = super::value_or_stdin(arg_matches, "token");
+            spec.verify_token(&token).map(|()| println!("valid"))
+        }
+        _ => unreachable!("possible subcommands are validated by clap"),
+    });
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            1
+        }
+    }
+}
+
+/// build api token spec from command line
+fn api_token_spec(arg_matches: &ArgMatches) -> anyhow::Result<ApiTokenSpec> {
+    let mut spec = ApiTokenSpec::default();
+    if let Some(prefix) = arg_matches.value_of("prefix") {
+        spec.prefix = prefix.to_string();
+    }
+    if let Some(body_len) = arg_matches.value_of("body_length") {
+        spec.body_len = body_len.parse().context("length must be positive integer")?;
+    }
+    if let Some(alphabet) = arg_matches.value_of("alphabet") {
+        spec.alphabet = alphabet.parse().expect("possible values are validated by clap");
+    }
+    if let Some(checksum) = arg_matches.value_of("checksum") {
+        spec.checksum = checksum.parse().expect("possible values are validated by clap");
+    }
+    Ok(spec)
+}
@@ -0,0 +1,461 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use passgen_core::{
+    generator::{CharClass, PasswordGenerator},
+    password::{zero_string, Password},
+    symbol, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Gauge, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+use std::io::{self, Write};
+
+use super::{generate, osc52, Helps};
+
+/// key bindings shown at the bottom
+pub const TUI_HELP: &str =
+    "tab/↑↓: move  ←→: length, mark  space: toggle  a/n: all marks  r: regenerate  c: copy  q: quit";
+
+/// `tui` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    let tui = SubCommand::with_name("tui")
+        .about("generate password in full-screen terminal interface, such as over SSH")
+        .arg(generate::length_arg(helps))
+        .arg(
+            Arg::with_name("settings")
+                .help("read settings of password in JSON from this file, instead of saved settings")
+                .long("settings")
+                .takes_value(true)
+                .value_name("FILE"),
+        );
+    generate::generator_args(tui, helps)
+}
+
+/// run terminal interface by `tui` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    let generator = super::base_settings(arg_matches)
+        .and_then(|settings| generate::generator_from_matches(arg_matches, settings));
+    let mut tui = match generator {
+        Ok(generator) => Tui::new(generator),
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            return 1;
+        }
+    };
+    let mut terminal = ratatui::init();
+    let result = tui.run(&mut terminal);
+    ratatui::restore();
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            1
+        }
+    }
+}
+
+/// settings which are focused in this order by tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Field {
+    Length,
+    Lower,
+    Upper,
+    Numeric,
+    Marks,
+    Addition,
+}
+
+impl Field {
+    pub const ALL: [Field; 6] =
+        [Field::Length, Field::Lower, Field::Upper, Field::Numeric, Field::Marks, Field::Addition];
+
+    /// return next field, or previous field if `back` is true
+    pub fn next(&self, back: bool) -> Self {
+        let i = Self::ALL.iter().position(|field| field == self).unwrap_or(0);
+        let len = Self::ALL.len();
+        Self::ALL[if back { (i + len - 1) % len } else { (i + 1) % len }]
+    }
+}
+
+/// state of terminal interface, same as generator pane of web
+pub struct Tui {
+    pub generator: PasswordGenerator,
+    /// generated password, or None if settings cannot generate password
+    pub password: Option<Password>,
+    pub focus: Field,
+    /// index of focused mark in `MarkSet::get_marks`
+    pub mark_cursor: usize,
+    /// addition as it is typed, which is normalized into generator
+    pub addition: String,
+    /// message of the last action or error
+    pub status: String,
+    pub quit: bool,
+}
+
+impl Tui {
+    pub fn new(mut generator: PasswordGenerator) -> Self {
+        // length out of the gauge, such as of saved settings, is clamped to start from valid one
+        generator.len = generator.len.clamp(PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH);
+        let addition = generator.addition.iter().collect();
+        let mut tui = Self {
+            generator,
+            password: None,
+            focus: Field::Length,
+            mark_cursor: 0,
+            addition,
+            status: String::new(),
+            quit: false,
+        };
+        tui.refresh_password();
+        tui
+    }
+
+    /// draw and handle key events until quit
+    pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key, &mut io::stdout())?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// generate password by current settings, or keep error message
+    pub fn refresh_password(&mut self) {
+        match self.generator.generate_password() {
+            Ok(password) => {
+                self.password = Some(password);
+                self.status.clear();
+            }
+            Err(error) => {
+                self.password = None;
+                self.status = error.to_string();
+            }
+        }
+    }
+
+    /// handle key, and write OSC 52 sequence to terminal if password is copied
+    pub fn handle_key<W: Write>(&mut self, key: KeyEvent, terminal: &mut W) -> io::Result<()> {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if ctrl => self.quit = true,
+            KeyCode::Char('r') if ctrl => self.refresh_password(),
+            KeyCode::Char('y') if ctrl => self.copy_password(terminal)?,
+            KeyCode::Tab | KeyCode::Down => self.focus = self.focus.next(false),
+            KeyCode::BackTab | KeyCode::Up => self.focus = self.focus.next(true),
+            // plain characters are typed into addition field
+            _ if self.focus == Field::Addition => self.edit_addition(key.code),
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('r') => self.refresh_password(),
+            KeyCode::Char('c') => self.copy_password(terminal)?,
+            code => self.edit_setting(code),
+        }
+        Ok(())
+    }
+
+    /// copy password into terminal clipboard through OSC 52
+    pub fn copy_password<W: Write>(&mut self, terminal: &mut W) -> io::Result<()> {
+        let password = match &self.password {
+            Some(password) => password,
+            None => return Ok(()),
+        };
+        let mut text = password.to_string();
+        let result = osc52::write_copy(terminal, &text, osc52::Multiplexer::detect(), None);
+        zero_string(&mut text);
+        result?;
+        self.status = "copied through OSC 52, if terminal supports it".to_string();
+        Ok(())
+    }
+
+    fn edit_setting(&mut self, code: KeyCode) {
+        let toggle = matches!(code, KeyCode::Char(' ') | KeyCode::Enter);
+        match (self.focus, code) {
+            (Field::Length, KeyCode::Left) => {
+                self.generator.len = self.generator.len.saturating_sub(1)
+            }
+            (Field::Length, KeyCode::Right) => self.generator.len += 1,
+            (Field::Length, KeyCode::PageDown) => {
+                self.generator.len = self.generator.len.saturating_sub(8)
+            }
+            (Field::Length, KeyCode::PageUp) => self.generator.len += 8,
+            (Field::Length, KeyCode::Home) => self.generator.len = PASSWORD_MIN_LENGTH,
+            (Field::Length, KeyCode::End) => self.generator.len = PASSWORD_MAX_LENGTH,
+            (Field::Lower, _) if toggle => self.generator.use_lower = !self.generator.use_lower,
+            (Field::Upper, _) if toggle => self.generator.use_upper = !self.generator.use_upper,
+            (Field::Numeric, _) if toggle => {
+                self.generator.use_numeric = !self.generator.use_numeric
+            }
+            (Field::Marks, KeyCode::Left) => self.move_mark_cursor(true),
+            (Field::Marks, KeyCode::Right) => self.move_mark_cursor(false),
+            (Field::Marks, KeyCode::Char('a')) => self.generator.mark.insert_all(),
+            (Field::Marks, KeyCode::Char('n')) => self.generator.mark.clear(),
+            (Field::Marks, _) if toggle => {
+                let mark = self.generator.mark.get_marks().nth(self.mark_cursor);
+                if let Some((mark, _)) = mark {
+                    self.generator.mark.toggle(mark);
+                }
+            }
+            _ => return,
+        }
+        self.generator.len = self.generator.len.clamp(PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH);
+        self.mark_cursor = self.mark_cursor.min(self.generator.mark.get_marks().count() - 1);
+        self.refresh_password();
+    }
+
+    fn move_mark_cursor(&mut self, back: bool) {
+        let len = self.generator.mark.get_marks().count();
+        self.mark_cursor =
+            if back { (self.mark_cursor + len - 1) % len } else { (self.mark_cursor + 1) % len };
+    }
+
+    fn edit_addition(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char(c) => self.addition.push(c),
+            KeyCode::Backspace => {
+                self.addition.pop();
+            }
+            _ => return,
+        }
+        self.generator.addition = symbol::normalized_chars(&self.addition);
+        self.refresh_password();
+    }
+
+    /// draw whole interface
+    pub fn draw(&self, frame: &mut Frame) {
+        let [preview, length, classes, marks, addition, help] = Layout::vertical([
+            Constraint::Min(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(5),
+            Constraint::Length(3),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+        self.draw_preview(frame, preview);
+        self.draw_length(frame, length);
+        self.draw_classes(frame, classes);
+        self.draw_marks(frame, marks);
+        let cursor = if self.focus == Field::Addition { "_" } else { "" };
+        let block = self.block("Addition", Field::Addition);
+        frame.render_widget(
+            Paragraph::new(format!("{}{}", self.addition, cursor)).block(block),
+            addition,
+        );
+        let help_text = vec![Line::raw(TUI_HELP), Line::styled(&self.status[..], Color::Yellow)];
+        frame.render_widget(Paragraph::new(help_text), help);
+    }
+
+    /// draw password, each character is colored by its class
+    fn draw_preview(&self, frame: &mut Frame, area: Rect) {
+        let line = match &self.password {
+            Some(password) => Line::from(
+                password
+                    .iter()
+                    .map(|c| Span::styled(c.to_string(), class_color(self.generator.classify(c))))
+                    .collect::<Vec<_>>(),
+            ),
+            None => Line::styled("cannot generate password", Color::Red),
+        };
+        let block = Block::bordered().title("Password");
+        frame.render_widget(Paragraph::new(line).block(block).wrap(Wrap { trim: false }), area);
+    }
+
+    fn draw_length(&self, frame: &mut Frame, area: Rect) {
+        let (len, min, max) = (self.generator.len, PASSWORD_MIN_LENGTH, PASSWORD_MAX_LENGTH);
+        let gauge = Gauge::default()
+            .block(self.block("Length", Field::Length))
+            .gauge_style(Color::Green)
+            .ratio((len.saturating_sub(min) as f64 / (max - min) as f64).min(1.0))
+            .label(len.to_string());
+        frame.render_widget(gauge, area);
+    }
+
+    fn draw_classes(&self, frame: &mut Frame, area: Rect) {
+        let checkboxes = [
+            (Field::Lower, CharClass::Lower.name(), self.generator.use_lower),
+            (Field::Upper, CharClass::Upper.name(), self.generator.use_upper),
+            (Field::Numeric, CharClass::Numeric.name(), self.generator.use_numeric),
+        ];
+        let spans: Vec<_> = checkboxes
+            .iter()
+            .flat_map(|&(field, name, used)| {
+                let check = if used { "[x] " } else { "[ ] " };
+                let style = match self.focus == field {
+                    true => Style::default().add_modifier(Modifier::REVERSED),
+                    false => Style::default(),
+                };
+                vec![Span::styled(format!("{}{}", check, name), style), Span::raw("  ")]
+            })
+            .collect();
+        let focus = matches!(self.focus, Field::Lower | Field::Upper | Field::Numeric);
+        let block = Block::bordered().title("Characters").border_style(border_color(focus));
+        frame.render_widget(Paragraph::new(Line::from(spans)).block(block), area);
+    }
+
+    /// draw available and unavailable marks, same as mark container of web
+    fn draw_marks(&self, frame: &mut Frame, area: Rect) {
+        let [available, unavailable] =
+            Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(area);
+        for (selected, title, area) in
+            [(true, "Available", available), (false, "Unavailable", unavailable)]
+        {
+            let spans: Vec<_> = self
+                .generator
+                .mark
+                .get_marks()
+                .enumerate()
+                .filter(|&(_, (_, available))| available == selected)
+                .map(|(i, (mark, _))| {
+                    let style = match self.focus == Field::Marks && i == self.mark_cursor {
+                        true => Style::default().add_modifier(Modifier::REVERSED),
+                        false => {
+                            Style::default().fg(if selected { Color::Green } else { Color::Gray })
+                        }
+                    };
+                    Span::styled(format!("{} ", mark), style)
+                })
+                .collect();
+            let block = self.block(title, Field::Marks);
+            frame.render_widget(
+                Paragraph::new(Line::from(spans)).block(block).wrap(Wrap { trim: false }),
+                area,
+            );
+        }
+    }
+
+    fn block<'a>(&self, title: &'a str, field: Field) -> Block<'a> {
+        Block::bordered().title(title).border_style(border_color(self.focus == field))
+    }
+}
+
+/// color of character class, close to colors of web
+pub fn class_color(class: CharClass) -> Color {
+    match class {
+        CharClass::Lower => Color::White,
+        CharClass::Upper => Color::LightGreen,
+        CharClass::Numeric => Color::Yellow,
+        CharClass::Mark => Color::Cyan,
+        CharClass::Addition => Color::Red,
+    }
+}
+
+fn border_color(focus: bool) -> Color {
+    if focus {
+        Color::Yellow
+    } else {
+        Color::Reset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, buffer::Buffer, Terminal};
+
+    fn render(tui: &Tui) -> Buffer {
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+        terminal.draw(|frame| tui.draw(frame)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    fn rows(buffer: &Buffer) -> Vec<String> {
+        let width = buffer.area.width as usize;
+        buffer
+            .content()
+            .chunks(width)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect())
+            .collect()
+    }
+
+    fn press(tui: &mut Tui, code: KeyCode) -> Vec<u8> {
+        let mut terminal = Vec::new();
+        tui.handle_key(KeyEvent::from(code), &mut terminal).unwrap();
+        terminal
+    }
+
+    #[test]
+    fn tui_preview_test() {
+        let tui = Tui::new(PasswordGenerator::default());
+        let password = tui.password.as_ref().unwrap().to_string();
+        let buffer = render(&tui);
+        let rows = rows(&buffer);
+        let y = rows.iter().position(|row| row.contains(&password)).unwrap();
+        let x = rows[y][..rows[y].find(&password).unwrap()].chars().count();
+        for (i, c) in password.chars().enumerate() {
+            let color = class_color(tui.generator.classify(&c));
+            assert_eq!(buffer[(x as u16 + i as u16, y as u16)].fg, color);
+        }
+        assert!(rows.iter().any(|row| row.contains("Available") && row.contains("Unavailable")));
+        assert!(rows.iter().any(|row| row.contains("[x] lower")));
+    }
+
+    #[test]
+    fn tui_out_of_range_length_test() {
+        for len in [0, 5, 500] {
+            let mut tui = Tui::new(PasswordGenerator { len, ..Default::default() });
+            assert!((PASSWORD_MIN_LENGTH..=PASSWORD_MAX_LENGTH).contains(&tui.generator.len));
+            assert!(tui.password.is_some());
+            render(&tui);
+            // keys can move length out of range too, which is drawn without panic
+            tui.generator.len = len;
+            render(&tui);
+        }
+    }
+
+    #[test]
+    fn tui_key_test() {
+        let mut tui = Tui::new(PasswordGenerator::default());
+        press(&mut tui, KeyCode::Right);
+        assert_eq!(tui.password.as_ref().unwrap().len(), 21);
+        press(&mut tui, KeyCode::Home);
+        press(&mut tui, KeyCode::Left);
+        assert_eq!(tui.generator.len, PASSWORD_MIN_LENGTH);
+
+        press(&mut tui, KeyCode::Tab);
+        press(&mut tui, KeyCode::Char(' '));
+        assert!(!tui.generator.use_lower);
+        assert!(tui.password.as_ref().unwrap().iter().all(|c| !c.is_ascii_lowercase()));
+        assert!(rows(&render(&tui)).iter().any(|row| row.contains("[ ] lower")));
+
+        press(&mut tui, KeyCode::Tab);
+        press(&mut tui, KeyCode::Tab);
+        press(&mut tui, KeyCode::Tab);
+        assert_eq!(tui.focus, Field::Marks);
+        press(&mut tui, KeyCode::Char('n'));
+        assert!(tui.generator.mark.is_empty());
+        let (first, _) = tui.generator.mark.get_marks().next().unwrap();
+        press(&mut tui, KeyCode::Enter);
+        assert!(tui.generator.mark.contains(&first));
+
+        press(&mut tui, KeyCode::Tab);
+        press(&mut tui, KeyCode::Char('q'));
+        assert!(!tui.quit);
+        assert_eq!(tui.addition, "q");
+        assert!(tui.password.as_ref().unwrap().iter().any(|&c| c == 'q'));
+        press(&mut tui, KeyCode::Backspace);
+        assert!(tui.generator.addition.is_empty());
+
+        press(&mut tui, KeyCode::Esc);
+        assert!(tui.quit);
+    }
+
+    #[test]
+    fn tui_copy_test() {
+        let mut tui = Tui::new(PasswordGenerator::default());
+        let password = tui.password.as_ref().unwrap().to_string();
+        let copied = press(&mut tui, KeyCode::Char('c'));
+        let sequence = osc52::Multiplexer::detect().wrap(&osc52::copy_sequence(&password));
+        assert_eq!(String::from_utf8(copied).unwrap(), sequence);
+        tui.password = None;
+        assert!(press(&mut tui, KeyCode::Char('c')).is_empty());
+    }
+}
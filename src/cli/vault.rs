@@ -0,0 +1,181 @@
+use anyhow::Context;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use passgen_core::{password::zero_string, vault::Vault};
+use std::path::{Path, PathBuf};
+
+use super::{generate, Helps};
+
+/// environment variable of vault file path
+pub const VAULT_FILE_ENV: &str = "PASSGEN_VAULT";
+
+/// environment variable of vault passphrase, for scripts without terminal
+pub const VAULT_PASSPHRASE_ENV: &str = "PASSGEN_VAULT_PASSPHRASE";
+
+/// `vault` subcommand
+pub fn command(helps: &Helps) -> App<'_, '_> {
+    let add = SubCommand::with_name("add")
+        .about("generate secret by the settings, and store it with the settings")
+        .arg(Arg::with_name("name").help("name of entry").required(true))
+        .arg(generate::length_arg(helps))
+        .arg(
+            Arg::with_name("settings")
+                .help("read settings of password in JSON from this file, instead of saved settings")
+                .long("settings")
+                .takes_value(true)
+                .value_name("FILE"),
+        );
+    SubCommand::with_name("vault")
+        .about("store generated secrets in local vault encrypted by passphrase")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg(
+            Arg::with_name("file")
+                .help(&helps.vault_file[..])
+                .long("file")
+                .takes_value(true)
+                .global(true),
+        )
+        .subcommand(generate::generator_args(add, helps))
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("print stored secret")
+                .arg(Arg::with_name("name").help("name of entry").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("print names, lengths and unix times of update of entries"),
+        )
+        .subcommand(
+            SubCommand::with_name("rotate")
+                .about("regenerate secret by its recorded settings")
+                .arg(Arg::with_name("name").help("name of entry").required(true)),
+        )
+        .subcommand(
+            SubCommand::with_name("rm")
+                .about("remove entry")
+                .arg(Arg::with_name("name").help("name of entry").required(true)),
+        )
+}
+
+/// manage vault by `vault` subcommand, and return exit code
+pub fn run(arg_matches: &ArgMatches) -> i32 {
+    let (command, arg_matches) = match arg_matches.subcommand() {
+        (command, Some(arg_matches)) => (command, arg_matches),
+        _ => unreachable!("subcommand is required by clap"),
+    };
+    let path = match vault_path(arg_matches) {
+        Ok(path) => path,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            return 1;
+        }
+    };
+    let name = arg_matches.value_of("name").unwrap_or("");
+    let result = open_vault(&path, command == "add").and_then(|mut vault| match command {
+        "add" => {
+            let settings =
+                generate::generator_from_matches(arg_matches, super::base_settings(arg_matches)?)?;
+            let secret = vault.add(name, settings)?;
+            vault.save(&path)?;
+            println!("{:?}", secret);
+            Ok(())
+        }
+        "get" => {
+            println!("{:?}", vault.get(name)?.secret()?);
+            Ok(())
+        }
+        "list" => {
+            for (name, entry) in vault.entries() {
+                let (len, unit) = (entry.settings.len, entry.settings.unit.name());
+                println!("{}\t{} {}\t{}", name, len, unit, entry.updated);
+            }
+            Ok(())
+        }
+        "rotate" => {
+            let secret = vault.rotate(name)?;
+            vault.save(&path)?;
+            println!("{:?}", secret);
+            Ok(())
+        }
+        "rm" => {
+            vault.remove(name)?;
+            vault.save(&path)
+        }
+        _ => unreachable!("possible subcommands are validated by clap"),
+    });
+    match result {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("{:?}", &error);
+            1
+        }
+    }
+}
+
+/// return path of vault file given by command line, environment variable, or default
+fn vault_path(arg_matches: &ArgMatches) -> anyhow::Result<PathBuf> {
+    if let Some(file) = arg_matches.value_of("file") {
+        return Ok(PathBuf::from(file));
+    }
+    if let Some(file) = std::env::var_os(VAULT_FILE_ENV) {
+        return Ok(PathBuf::from(file));
+    }
+    let data_home = match std::env::var_os("XDG_DATA_HOME") {
+        Some(data_home) => PathBuf::from(data_home),
+        None => {
+            let home = std::env::var_os("HOME").context("HOME or PASSGEN_VAULT should be set")?;
+            PathBuf::from(home).join(".local").join("share")
+        }
+    };
+    Ok(data_home.join("passgen").join("vault"))
+}
+
+/// open vault file by passphrase, or create new vault if it does not exist and creatable
+fn open_vault(path: &Path, creatable: bool) -> anyhow::Result<Vault> {
+    let exists = path.exists();
+    let mut passphrase = if exists {
+        read_passphrase("vault passphrase: ")?
+    } else if creatable {
+        eprintln!("create new vault {}", path.display());
+        read_passphrase("new vault passphrase: ")?
+    } else {
+        anyhow::bail!("vault {} does not exist, add entry first", path.display())
+    };
+    let vault = match exists {
+        true => Vault::load(path, &passphrase),
+        false => create_vault(path, &passphrase),
+    };
+    zero_string(&mut passphrase);
+    vault
+}
+
+/// create new vault by passphrase, after it is confirmed and directory of path is created
+fn create_vault(path: &Path, passphrase: &str) -> anyhow::Result<Vault> {
+    if std::env::var_os(VAULT_PASSPHRASE_ENV).is_none() {
+        let mut confirmation = read_passphrase("confirm vault passphrase: ")?;
+        let matched = passphrase == confirmation;
+        zero_string(&mut confirmation);
+        anyhow::ensure!(matched, "passphrases do not match");
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    Vault::create(passphrase)
+}
+
+/// read passphrase from environment variable, or prompt it without echo
+fn read_passphrase(prompt: &str) -> anyhow::Result<String> {
+    if let Ok(passphrase) = std::env::var(VAULT_PASSPHRASE_ENV) {
+        return Ok(passphrase);
+    }
+    #[cfg(not(target_os = "wasi"))]
+    let passphrase = rpassword::prompt_password(prompt)?;
+    #[cfg(target_os = "wasi")]
+    let passphrase = {
+        // wasi has no terminal control, so passphrase is read from stdin as it is
+        eprint!("{}", prompt);
+        let mut line = String::new();
+        std::io::stdin().read_line(&mut line)?;
+        line.trim_end_matches(&['\r', '\n'][..]).to_string()
+    };
+    Ok(passphrase)
+}
@@ -1,4 +1,6 @@
-use super::{generator_pane::GeneratorPane, header::Header};
+use super::{
+    generator_pane::GeneratorPane, header::Header, otp_pane::OtpPane, token_pane::TokenPane,
+};
 use yew::prelude::*;
 
 pub struct PassGenApp {}
@@ -27,6 +29,8 @@ impl Component for PassGenApp {
                 </header>
                 <main>
                     <GeneratorPane />
+                    <TokenPane />
+                    <OtpPane />
                 </main>
             </>
         }
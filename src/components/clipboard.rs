@@ -0,0 +1,62 @@
+use wasm_bindgen::JsValue;
+use yew::Callback;
+
+/// choices of seconds to clear clipboard after copy, 0 means never
+pub const CLIPBOARD_CLEAR_SECONDS: [u32; 4] = [0, 15, 30, 60];
+
+/// return Clipboard API, which is unavailable on insecure contexts such as http
+pub fn clipboard() -> Result<web_sys::Clipboard, String> {
+    let navigator = yew::utils::window().navigator();
+    match js_sys::Reflect::get(&navigator, &"clipboard".into()) {
+        Ok(clipboard) if !clipboard.is_undefined() && !clipboard.is_null() => {
+            Ok(navigator.clipboard())
+        }
+        _ => Err("Clipboard API is unavailable in this context".to_string()),
+    }
+}
+
+/// write text into clipboard, and call back with its result
+pub fn write_text(text: &str, callback: Callback<Result<(), String>>) {
+    let promise = match clipboard() {
+        Ok(clipboard) => clipboard.write_text(text),
+        Err(error) => return callback.emit(Err(error)),
+    };
+    let task = async move {
+        let result = wasm_bindgen_futures::JsFuture::from(promise).await;
+        callback.emit(result.map(|_| ()).map_err(error_message));
+    };
+    wasm_bindgen_futures::spawn_local(task);
+}
+
+/// read text from clipboard, and call back with its result.
+/// browsers may ask permission, or reject it without user activation
+pub fn read_text(callback: Callback<Result<String, String>>) {
+    let promise = match clipboard() {
+        Ok(clipboard) => clipboard.read_text(),
+        Err(error) => return callback.emit(Err(error)),
+    };
+    let task = async move {
+        let result = wasm_bindgen_futures::JsFuture::from(promise).await;
+        callback
+            .emit(result.map(|text| text.as_string().unwrap_or_default()).map_err(error_message));
+    };
+    wasm_bindgen_futures::spawn_local(task);
+}
+
+/// select text of element, so that user can copy it by keyboard instead of Clipboard API
+pub fn select_text(id: &str) -> Result<(), String> {
+    let window = yew::utils::window();
+    let element = yew::utils::document()
+        .get_element_by_id(id)
+        .ok_or_else(|| format!("element {} is not found", id))?;
+    let selection = window.get_selection().map_err(error_message)?.ok_or("cannot select text")?;
+    selection.select_all_children(&element).map_err(error_message)
+}
+
+/// message of rejected promise, such as NotAllowedError
+fn error_message(error: JsValue) -> String {
+    js_sys::Reflect::get(&error, &"message".into())
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| format!("{:?}", error))
+}
@@ -1,6 +1,14 @@
-use crate::password::{Password, PasswordGenerator, PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH};
+use super::{clipboard, clipboard::CLIPBOARD_CLEAR_SECONDS, qr_view::QrView, speech, to_html};
+use crate::password::{
+    normalized_chars, zero_string, Escape, Password, PasswordGenerator, Reading, UnicodeClass,
+    PASSWORD_MAX_LENGTH, PASSWORD_MIN_LENGTH, PHONETIC_DEFAULT_CHUNK,
+};
+use std::time::Duration;
 use wasm_bindgen::JsCast;
-use yew::prelude::*;
+use yew::{
+    prelude::*,
+    services::{interval::IntervalTask, IntervalService},
+};
 use yew_styles::{
     button::Button,
     card::Card,
@@ -21,6 +29,28 @@ pub struct GeneratorPane {
     link: ComponentLink<Self>,
     generator: PasswordGenerator,
     password: Password,
+    copy_status: CopyStatus,
+    /// text written into clipboard, which is compared before clearing it
+    copied: String,
+    select_pending: bool,
+    clear_seconds: u32,
+    clear_remaining: u32,
+    clear_interval: Option<IntervalTask>,
+    /// show how to read password aloud under the password card
+    phonetic: bool,
+    reading: Reading,
+    speech_error: Option<String>,
+}
+
+/// feedback of the last copy, shown under copy buttons
+pub enum CopyStatus {
+    Idle,
+    Copied,
+    /// Clipboard API failed with the reason, so copied text is selected instead
+    Selected(String),
+    Cleared,
+    /// clipboard is not cleared with the reason, such as copy of other text
+    NotCleared(String),
 }
 
 pub enum Msg {
@@ -29,23 +59,50 @@ pub enum Msg {
     ToggleLower,
     ToggleUpper,
     ToggleNumeric,
+    ToggleUnicode(UnicodeClass),
     ToggleMark(char),
-    CopyPassword,
+    CopyPassword(Option<Escape>),
+    Copied(Result<(), String>),
+    SelectClearSeconds(u32),
+    ClearTick,
+    ClipboardRead(Result<String, String>),
+    Cleared(Result<(), String>),
     DraggedMark(DragEvent),
     DragOverMark(DragEvent),
     DroppedMark(DragEvent, bool),
     ToggleAllMark(bool),
     InputAddition(String),
+    InputContext(usize, String),
+    TogglePhonetic,
+    SelectReading(Reading),
+    ReadAloud,
 }
 
+/// labels of context form, such as username, which should not be contained in password
+pub const CONTEXT_LABELS: [&str; 3] = ["Username", "Email", "Service"];
+
 impl Component for GeneratorPane {
     type Message = Msg;
     type Properties = ();
     fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
-        let generator = PasswordGenerator::default();
+        let mut generator = PasswordGenerator::default();
+        generator.context = vec![String::new(); CONTEXT_LABELS.len()];
         let password =
             generator.generate_password().expect("default generator should generate password.");
-        Self { link, generator, password }
+        Self {
+            link,
+            generator,
+            password,
+            copy_status: CopyStatus::Idle,
+            copied: String::new(),
+            select_pending: false,
+            clear_seconds: 0,
+            clear_remaining: 0,
+            clear_interval: None,
+            phonetic: false,
+            reading: Reading::default(),
+            speech_error: None,
+        }
     }
 
     fn update(&mut self, msg: Self::Message) -> ShouldRender {
@@ -55,13 +112,69 @@ impl Component for GeneratorPane {
             Msg::ToggleLower => self.generator.use_lower = !self.generator.use_lower,
             Msg::ToggleUpper => self.generator.use_upper = !self.generator.use_upper,
             Msg::ToggleNumeric => self.generator.use_numeric = !self.generator.use_numeric,
+            Msg::ToggleUnicode(class) => {
+                if !self.generator.unicode.remove(&class) {
+                    self.generator.unicode.insert(class);
+                }
+            }
             Msg::ToggleMark(mark) => {
                 self.generator.mark.toggle(mark);
             }
-            Msg::CopyPassword => {
-                self.copy_password_to_clipboard();
+            Msg::CopyPassword(escape) => {
+                self.copy_password_to_clipboard(escape);
                 return false; // do not refresh password
             }
+            Msg::Copied(Ok(())) => {
+                self.copy_status = CopyStatus::Copied;
+                // countdown of previous copy is cancelled, even if this copy is not cleared
+                self.clear_interval = None;
+                if self.clear_seconds > 0 {
+                    let callback = self.link.callback(|_| Msg::ClearTick);
+                    self.clear_interval =
+                        Some(IntervalService::spawn(Duration::from_secs(1), callback));
+                    self.clear_remaining = self.clear_seconds;
+                }
+                return true; // do not refresh password
+            }
+            Msg::Copied(Err(error)) => {
+                self.clear_interval = None;
+                self.copy_status = CopyStatus::Selected(error);
+                self.select_pending = true;
+                return true; // do not refresh password
+            }
+            Msg::SelectClearSeconds(seconds) => {
+                self.clear_seconds = seconds;
+                return false; // do not refresh password
+            }
+            Msg::ClearTick => {
+                self.clear_remaining = self.clear_remaining.saturating_sub(1);
+                if self.clear_remaining == 0 {
+                    self.clear_interval = None;
+                    clipboard::read_text(self.link.callback(Msg::ClipboardRead));
+                }
+                return true; // do not refresh password
+            }
+            Msg::ClipboardRead(Ok(mut text)) => {
+                if text == self.copied {
+                    clipboard::write_text("", self.link.callback(Msg::Cleared));
+                } else {
+                    self.forget_copied();
+                    let reason = "it was overwritten by other copy".to_string();
+                    self.copy_status = CopyStatus::NotCleared(reason);
+                }
+                zero_string(&mut text);
+                return true; // do not refresh password
+            }
+            Msg::ClipboardRead(Err(error)) | Msg::Cleared(Err(error)) => {
+                self.forget_copied();
+                self.copy_status = CopyStatus::NotCleared(error);
+                return true; // do not refresh password
+            }
+            Msg::Cleared(Ok(())) => {
+                self.forget_copied();
+                self.copy_status = CopyStatus::Cleared;
+                return true; // do not refresh password
+            }
             Msg::DraggedMark(drag_event) => {
                 self.dragged_mark_tag(drag_event);
                 return false; // do not refresh password
@@ -78,7 +191,23 @@ impl Component for GeneratorPane {
                     self.generator.mark.clear()
                 }
             }
-            Msg::InputAddition(addition) => self.generator.addition = addition.chars().collect(),
+            Msg::InputAddition(addition) => self.generator.addition = normalized_chars(&addition),
+            Msg::InputContext(i, context) => self.generator.context[i] = context,
+            Msg::TogglePhonetic => {
+                self.phonetic = !self.phonetic;
+                if !self.phonetic {
+                    speech::cancel();
+                }
+                return true; // do not refresh password
+            }
+            Msg::SelectReading(reading) => {
+                self.reading = reading;
+                return true; // do not refresh password
+            }
+            Msg::ReadAloud => {
+                self.speech_error = speech::speak(&self.phonetic_text(), self.reading).err();
+                return true; // do not refresh password
+            }
         }
         self.refresh_password()
     }
@@ -90,10 +219,24 @@ impl Component for GeneratorPane {
     fn view(&self) -> Html {
         self.view_main()
     }
+
+    fn rendered(&mut self, _first_render: bool) {
+        if self.select_pending {
+            self.select_pending = false;
+            if let Err(error) = clipboard::select_text("copy-fallback") {
+                web_sys::console::log_1(&error.into());
+            }
+        }
+    }
 }
 
 impl GeneratorPane {
     pub fn refresh_password(&mut self) -> ShouldRender {
+        if let CopyStatus::Selected(_) = self.copy_status {
+            // selected text of old password should not be left on screen
+            self.copy_status = CopyStatus::Idle;
+        }
+        speech::cancel();
         match self.generator.generate_password() {
             Ok(password) => self.password = password,
             Err(error) => web_sys::console::log_2(
@@ -104,14 +247,24 @@ impl GeneratorPane {
         true
     }
 
-    pub fn copy_password_to_clipboard(&self) {
-        // TODO: error handling
-        let password = self.password.to_string(); // this may have subtle security warning
-        let task = async move {
-            let promise = yew::utils::window().navigator().clipboard().write_text(&password);
-            let _result = wasm_bindgen_futures::JsFuture::from(promise).await;
+    /// copy password into clipboard, whose result is notified by `Msg::Copied`
+    pub fn copy_password_to_clipboard(&mut self, escape: Option<Escape>) {
+        self.forget_copied();
+        let mut text = self.password.to_string();
+        self.copied = match escape {
+            Some(escape) => {
+                let escaped = escape.escape(&text);
+                zero_string(&mut text);
+                escaped
+            }
+            None => text, // this may have subtle security warning
         };
-        wasm_bindgen_futures::spawn_local(task);
+        clipboard::write_text(&self.copied, self.link.callback(Msg::Copied));
+    }
+
+    /// zero-fill copied text, after clipboard is cleared or it is no longer ours
+    pub fn forget_copied(&mut self) {
+        zero_string(&mut self.copied);
     }
 
     pub fn dragged_mark_tag(&mut self, drag_event: DragEvent) {
@@ -150,6 +303,15 @@ impl GeneratorPane {
                 <Item class_name="fix-width" layouts=vec![ItemLayout::ItXs(12)]>
                     { self.view_generated_password() }
                 </Item>
+                <Item layouts=vec![ItemLayout::ItXs(12)]>
+                    { self.view_phonetic() }
+                </Item>
+                <Item layouts=vec![ItemLayout::ItXs(12)]>
+                    { self.view_copy_as_buttons() }
+                </Item>
+                <Item layouts=vec![ItemLayout::ItXs(12)]>
+                    <QrView secret=self.password.to_string() />
+                </Item>
                 <Container direction=Direction::Row wrap=Wrap::Wrap>
                     <Item layouts=vec![ItemLayout::ItXs(7)]>
                         { self.view_setting_pane() }
@@ -172,18 +334,166 @@ impl GeneratorPane {
                     card_size=Size::Medium
                     card_palette=Palette::Link
                     card_style=Style::Light
-                    onclick_signal=self.link.callback(|_| Msg::CopyPassword)
+                    onclick_signal=self.link.callback(|_| Msg::CopyPassword(None))
                     header=Some(html!{
                         <b>{ "Generated Password" }</b>
                     })
                     body=Some(html!{
-                        <p id="password-display">{self.generator.to_html(&self.password)}</p>
+                        <p id="password-display">{ to_html(&self.generator, &self.password) }</p>
                     })
                 />
             </div>
         }
     }
 
+    /// return phonetic words of password, where chunks are separated by pause
+    pub fn phonetic_text(&self) -> String {
+        self.reading
+            .chunks(&self.password.to_string(), PHONETIC_DEFAULT_CHUNK)
+            .iter()
+            .map(|chunk| chunk.words.join(", "))
+            .collect::<Vec<_>>()
+            .join(". ")
+    }
+
+    pub fn view_phonetic(&self) -> Html {
+        let toggle = html! {
+            <>
+                <input
+                    id="phonetic-checkbox"
+                    type="checkbox"
+                    checked=self.phonetic
+                    onclick=self.link.callback(|_| Msg::TogglePhonetic)
+                />
+                <label for="phonetic-checkbox">{ " Phonetic" }</label>
+            </>
+        };
+        if !self.phonetic {
+            return html! { <div id="phonetic" title="Show how to read password aloud">{ toggle }</div> };
+        }
+        let onchange = self.link.callback(|change: ChangeData| match change {
+            ChangeData::Select(select) => {
+                Msg::SelectReading(select.value().parse().unwrap_or_default())
+            }
+            _ => Msg::SelectReading(Reading::default()),
+        });
+        let options: Html = Reading::ALL
+            .iter()
+            .map(|&reading| {
+                html! {
+                    <option value=reading.name() selected=reading == self.reading>{ reading.name() }</option>
+                }
+            })
+            .collect();
+        let chunks: Html = self
+            .reading
+            .chunks(&self.password.to_string(), PHONETIC_DEFAULT_CHUNK)
+            .into_iter()
+            .map(|chunk| {
+                html! {
+                    <tr>
+                        <td>{ format!("{}-{}", chunk.position, chunk.end()) }</td>
+                        <td><code>{ &chunk.text }</code></td>
+                        <td>{ chunk.words.join(", ") }</td>
+                    </tr>
+                }
+            })
+            .collect();
+        let speech_error = match &self.speech_error {
+            Some(error) => {
+                html! { <p class="copy-failure">{ format!("Cannot read aloud: {}.", error) }</p> }
+            }
+            None => html! {},
+        };
+        html! {
+            <div id="phonetic" title="Show how to read password aloud">
+                { toggle }
+                <select id="phonetic-reading-select" onchange=onchange>{ options }</select>
+                <Button
+                    id="read-aloud-button"
+                    onclick_signal=self.link.callback(|_| Msg::ReadAloud)
+                    button_palette=Palette::Link
+                    button_style=Style::Outline
+                    button_size=Size::Small
+                >{ "Read aloud" }</Button>
+                { speech_error }
+                <table id="phonetic-chunks">{ chunks }</table>
+            </div>
+        }
+    }
+
+    pub fn view_copy_as_buttons(&self) -> Html {
+        let buttons: Html = Escape::ALL
+            .iter()
+            .map(|&escape| {
+                html! {
+                    <Button
+                        id=format!("copy-as-{}-button", escape.name())
+                        onclick_signal=self.link.callback(move |_| Msg::CopyPassword(Some(escape)))
+                        button_palette=Palette::Link
+                        button_style=Style::Outline
+                        button_size=Size::Small
+                    >{ escape.name() }</Button>
+                }
+            })
+            .collect();
+        let onchange = self.link.callback(|change: ChangeData| match change {
+            ChangeData::Select(select) => {
+                Msg::SelectClearSeconds(select.value().parse().unwrap_or(0))
+            }
+            _ => Msg::SelectClearSeconds(0),
+        });
+        let options: Html = CLIPBOARD_CLEAR_SECONDS
+            .iter()
+            .map(|&seconds| {
+                let text = if seconds == 0 { "never".to_string() } else { format!("{}s", seconds) };
+                html! {
+                    <option value=seconds.to_string() selected=seconds == self.clear_seconds>{ text }</option>
+                }
+            })
+            .collect();
+        html! {
+            <>
+                <div id="copy-as" title="Copy password quoted or escaped for the context">
+                    <span>{ "Copy as\u{2026} " }</span>
+                    { buttons }
+                    <label for="clear-clipboard-select">{ " Clear clipboard after " }</label>
+                    <select id="clear-clipboard-select" onchange=onchange>{ options }</select>
+                </div>
+                { self.view_copy_status() }
+            </>
+        }
+    }
+
+    pub fn view_copy_status(&self) -> Html {
+        match &self.copy_status {
+            CopyStatus::Idle => html! {},
+            CopyStatus::Copied if self.clear_interval.is_some() => html! {
+                <p id="copy-status" class="copy-success">
+                    { format!("Copied! Clipboard will be cleared in {}s.", self.clear_remaining) }
+                </p>
+            },
+            CopyStatus::Copied => html! {
+                <p id="copy-status" class="copy-success">{ "Copied!" }</p>
+            },
+            CopyStatus::Selected(error) => html! {
+                <p id="copy-status" class="copy-failure">
+                    { format!("Cannot copy: {}. Password is selected, press Ctrl+C to copy it.", error) }
+                    <br />
+                    <code id="copy-fallback">{ &self.copied }</code>
+                </p>
+            },
+            CopyStatus::Cleared => html! {
+                <p id="copy-status" class="copy-success">{ "Clipboard is cleared." }</p>
+            },
+            CopyStatus::NotCleared(reason) => html! {
+                <p id="copy-status" class="copy-failure">
+                    { format!("Clipboard is not cleared, because {}.", reason) }
+                </p>
+            },
+        }
+    }
+
     pub fn view_generate_button(&self) -> Html {
         match self.generator.can_generate() {
             Ok(()) => html! {
@@ -225,6 +535,9 @@ impl GeneratorPane {
                         <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
                             { self.view_addition_form() }
                         </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            { self.view_context_form() }
+                        </Item>
                     </Container>
                 })
             />
@@ -248,6 +561,31 @@ impl GeneratorPane {
         }
     }
 
+    pub fn view_context_form(&self) -> Html {
+        CONTEXT_LABELS
+            .iter()
+            .enumerate()
+            .map(|(i, &label)| {
+                let id = format!("context-form-{}", label.to_lowercase());
+                html! {
+                    <FormGroup orientation=Orientation::Horizontal>
+                        <FormLabel
+                            text=label
+                            label_for=id.clone()
+                        />
+                        <FormInput
+                            id=id
+                            input_type=InputType::Text
+                            input_size=Size::Medium
+                            placeholder="optional, not contained in password"
+                            oninput_signal=self.link.callback(move |d: InputData| Msg::InputContext(i, d.value))
+                        />
+                    </FormGroup>
+                }
+            })
+            .collect()
+    }
+
     pub fn view_length_bar(&self) -> Html {
         let oninput = self.link.callback(|d: InputData| {
             Msg::EditLength(d.value.parse().expect("range type input should have only integer."))
@@ -289,6 +627,7 @@ impl GeneratorPane {
                         <Item layouts=vec![ItemLayout::ItM(6)] align_self=AlignSelf::Stretch>
                             { self.view_numeric_checkbox() }
                         </Item>
+                        { self.view_unicode_checkboxes() }
                     </Container>
                 })
             />
@@ -352,6 +691,33 @@ impl GeneratorPane {
         }
     }
 
+    pub fn view_unicode_checkboxes(&self) -> Html {
+        UnicodeClass::ALL
+            .iter()
+            .map(|&class| {
+                let id = format!("{}-checkbox", class.name());
+                let text = class.chars().take(3).chain("\u{2026}".chars()).collect::<String>();
+                html! {
+                    <Item layouts=vec![ItemLayout::ItM(6)] align_self=AlignSelf::Stretch>
+                        <FormGroup orientation=Orientation::Horizontal>
+                            <FormLabel
+                                text=text
+                                label_for=id.clone()
+                            />
+                            <FormInput
+                                id=id
+                                input_type=InputType::Checkbox
+                                input_size=Size::Medium
+                                oninput_signal=self.link.callback(move |_| Msg::ToggleUnicode(class))
+                                checked=self.generator.unicode.contains(&class)
+                            />
+                        </FormGroup>
+                    </Item>
+                }
+            })
+            .collect()
+    }
+
     pub fn view_mark_container(&self) -> Html {
         html! {
             <Container wrap=Wrap::Wrap direction=Direction::Row>
@@ -0,0 +1,235 @@
+use crate::password::{Otp, OtpKind, Password, OTP_DEFAULT_SECRET_BYTES};
+use std::time::Duration;
+use yew::{
+    prelude::*,
+    services::{interval::IntervalTask, IntervalService},
+};
+use yew_styles::{
+    button::Button,
+    card::Card,
+    forms::{
+        form_group::{FormGroup, Orientation},
+        form_input::{FormInput, InputType},
+        form_label::FormLabel,
+    },
+    layouts::{
+        container::{Container, Direction, JustifyContent, Mode, Wrap},
+        item::{AlignSelf, Item, ItemLayout},
+    },
+    styles::{Palette, Size, Style},
+    text::{Text, TextType},
+};
+
+pub struct OtpPane {
+    link: ComponentLink<Self>,
+    otp: Otp,
+    issuer: String,
+    account: String,
+    now: u64,
+    _interval: IntervalTask,
+}
+
+pub enum Msg {
+    Generate,
+    InputIssuer(String),
+    InputAccount(String),
+    Tick,
+    CopySecret,
+    CopyUri,
+}
+
+impl Component for OtpPane {
+    type Message = Msg;
+    type Properties = ();
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let otp =
+            Otp::generate(OTP_DEFAULT_SECRET_BYTES).expect("default otp should be generated.");
+        let interval = IntervalService::spawn(Duration::from_secs(1), link.callback(|_| Msg::Tick));
+        let (issuer, account) = (String::new(), String::new());
+        Self { link, otp, issuer, account, now: Self::unix_time(), _interval: interval }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Generate => match Otp::generate(OTP_DEFAULT_SECRET_BYTES) {
+                Ok(otp) => self.otp = otp,
+                Err(error) => web_sys::console::log_1(&error.to_string().into()),
+            },
+            Msg::InputIssuer(issuer) => self.issuer = issuer,
+            Msg::InputAccount(account) => self.account = account,
+            Msg::Tick => {
+                let now = Self::unix_time();
+                let changed = now != self.now;
+                self.now = now;
+                return changed;
+            }
+            Msg::CopySecret => {
+                if let Ok(secret) = self.otp.secret_base32() {
+                    Self::copy_to_clipboard(&secret);
+                }
+                return false;
+            }
+            Msg::CopyUri => {
+                if let Ok(uri) = self.uri() {
+                    Self::copy_to_clipboard(&uri);
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        self.view_main()
+    }
+}
+
+impl OtpPane {
+    /// current unix time of browser in seconds
+    pub fn unix_time() -> u64 {
+        (js_sys::Date::now() / 1000.0) as u64
+    }
+
+    pub fn uri(&self) -> anyhow::Result<Password> {
+        let account = if self.account.is_empty() { "account" } else { &self.account[..] };
+        self.otp.uri(OtpKind::Totp, &self.issuer, account)
+    }
+
+    pub fn copy_to_clipboard(text: &Password) {
+        let text = text.to_string();
+        let task = async move {
+            let promise = yew::utils::window().navigator().clipboard().write_text(&text);
+            let _result = wasm_bindgen_futures::JsFuture::from(promise).await;
+        };
+        wasm_bindgen_futures::spawn_local(task);
+    }
+
+    pub fn view_main(&self) -> Html {
+        html! {
+            <Container direction=Direction::Column wrap=Wrap::Wrap justify_content=JustifyContent::Center(Mode::NoMode)>
+                <Item class_name="fix-width" layouts=vec![ItemLayout::ItXs(12)]>
+                    { self.view_code() }
+                </Item>
+                <Item class_name="fix-width" layouts=vec![ItemLayout::ItXs(12)]>
+                    { self.view_secret() }
+                </Item>
+                <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                    { self.view_otp_setting_pane() }
+                </Item>
+            </Container>
+        }
+    }
+
+    pub fn view_code(&self) -> Html {
+        let next = self.now.saturating_add(self.otp.period());
+        html! {
+            <Card
+                card_size=Size::Medium
+                card_palette=Palette::Link
+                card_style=Style::Light
+                interaction_effect=false
+                header=Some(html!{
+                    <b>{ format!("TOTP Code (next in {} seconds)", self.otp.remaining(self.now)) }</b>
+                })
+                body=Some(html!{
+                    <p id="otp-code-display">
+                        <Text
+                            text_type=TextType::Plain
+                            text_size=Size::Big
+                            plain_text=format!("{}  next: {}", self.otp.totp(self.now), self.otp.totp(next))
+                            html_text=None
+                        />
+                    </p>
+                })
+            />
+        }
+    }
+
+    pub fn view_secret(&self) -> Html {
+        let secret = self.otp.secret_base32().map(|s| s.to_string()).unwrap_or_default();
+        let uri = self.uri().map(|u| u.to_string()).unwrap_or_default();
+        html! {
+            <Card
+                card_size=Size::Medium
+                card_palette=Palette::Link
+                card_style=Style::Light
+                interaction_effect=false
+                header=Some(html!{
+                    <b>{ format!("Secret ({} bits, {})", self.otp.secret_len() * 8, self.otp.algorithm.name()) }</b>
+                })
+                body=Some(html!{
+                    <>
+                        <p id="otp-secret-display" title="Click to copy secret!" onclick=self.link.callback(|_| Msg::CopySecret)>
+                            <Text
+                                text_type=TextType::Plain
+                                text_size=Size::Medium
+                                plain_text=secret
+                                html_text=None
+                            />
+                        </p>
+                        <p id="otp-uri-display" title="Click to copy otpauth URI!" onclick=self.link.callback(|_| Msg::CopyUri)>
+                            <Text
+                                text_type=TextType::Plain
+                                text_size=Size::Small
+                                plain_text=uri
+                                html_text=None
+                            />
+                        </p>
+                    </>
+                })
+            />
+        }
+    }
+
+    pub fn view_otp_setting_pane(&self) -> Html {
+        html! {
+            <Card
+                card_size=Size::Small
+                card_palette=Palette::Primary
+                card_style=Style::Light
+                interaction_effect=false
+                single_content=Some(html!{
+                    <Container direction=Direction::Column wrap=Wrap::Wrap>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::FlexEnd>
+                            <Button
+                                id="generate-otp-button"
+                                onclick_signal=self.link.callback(|_| Msg::Generate)
+                                button_palette=Palette::Success
+                                button_style=Style::Light
+                                button_size=Size::Small
+                            >{ "Generate Secret!" }</Button>
+                        </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            <FormGroup orientation=Orientation::Horizontal>
+                                <FormLabel text="Issuer: " label_for="otp-issuer-form" />
+                                <FormInput
+                                    id="otp-issuer-form"
+                                    input_type=InputType::Text
+                                    input_size=Size::Medium
+                                    placeholder="service name"
+                                    oninput_signal=self.link.callback(|d: InputData| Msg::InputIssuer(d.value))
+                                />
+                            </FormGroup>
+                        </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            <FormGroup orientation=Orientation::Horizontal>
+                                <FormLabel text="Account: " label_for="otp-account-form" />
+                                <FormInput
+                                    id="otp-account-form"
+                                    input_type=InputType::Text
+                                    input_size=Size::Medium
+                                    placeholder="such as email"
+                                    oninput_signal=self.link.callback(|d: InputData| Msg::InputAccount(d.value))
+                                />
+                            </FormGroup>
+                        </Item>
+                    </Container>
+                })
+            />
+        }
+    }
+}
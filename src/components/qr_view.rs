@@ -0,0 +1,146 @@
+use crate::password::{Qr, WifiNetwork};
+use std::time::Duration;
+use yew::{
+    prelude::*,
+    services::{interval::IntervalTask, IntervalService},
+};
+use yew_styles::{
+    button::Button,
+    forms::{
+        form_group::{FormGroup, Orientation},
+        form_input::{FormInput, InputType},
+        form_label::FormLabel,
+    },
+    styles::{Palette, Size, Style},
+};
+
+/// QR code is hidden automatically after this seconds, not to be left on screen
+pub const QR_VIEW_AUTO_HIDE_SECONDS: u32 = 30;
+
+/// QR code of secret, which appears only after click
+pub struct QrView {
+    link: ComponentLink<Self>,
+    props: Props,
+    ssid: String,
+    remaining: u32,
+    interval: Option<IntervalTask>,
+}
+
+#[derive(Properties, Clone, PartialEq)]
+pub struct Props {
+    pub secret: String,
+}
+
+pub enum Msg {
+    Show,
+    Hide,
+    Tick,
+    InputSsid(String),
+}
+
+impl Component for QrView {
+    type Message = Msg;
+    type Properties = Props;
+    fn create(props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        Self { link, props, ssid: String::new(), remaining: 0, interval: None }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Show => {
+                let callback = self.link.callback(|_| Msg::Tick);
+                self.interval = Some(IntervalService::spawn(Duration::from_secs(1), callback));
+                self.remaining = QR_VIEW_AUTO_HIDE_SECONDS;
+            }
+            Msg::Hide => self.interval = None,
+            Msg::Tick => {
+                self.remaining = self.remaining.saturating_sub(1);
+                if self.remaining == 0 {
+                    self.interval = None;
+                }
+            }
+            Msg::InputSsid(ssid) => self.ssid = ssid,
+        }
+        true
+    }
+
+    fn change(&mut self, props: Self::Properties) -> ShouldRender {
+        if self.props != props {
+            // QR code of old secret should not be shown
+            self.props = props;
+            self.interval = None;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn view(&self) -> Html {
+        html! {
+            <div id="qr-view">
+                { self.view_qr_setting() }
+                { if self.interval.is_some() { self.view_qr() } else { html! {} } }
+            </div>
+        }
+    }
+}
+
+impl QrView {
+    /// return QR code of Wi-Fi credential if ssid is given, otherwise QR code of secret
+    pub fn qr(&self) -> anyhow::Result<Qr> {
+        if self.ssid.is_empty() {
+            Qr::new(&self.props.secret)
+        } else {
+            Qr::new(&WifiNetwork::new(&self.ssid).payload(&self.props.secret)?.to_string())
+        }
+    }
+
+    pub fn view_qr_setting(&self) -> Html {
+        let shown = self.interval.is_some();
+        let text = if shown {
+            format!("Hide QR code ({}s)", self.remaining)
+        } else {
+            "Show QR code".to_string()
+        };
+        html! {
+            <FormGroup orientation=Orientation::Horizontal>
+                <Button
+                    id="qr-button"
+                    onclick_signal=self.link.callback(move |_| if shown { Msg::Hide } else { Msg::Show })
+                    button_palette=Palette::Info
+                    button_style=Style::Outline
+                    button_size=Size::Small
+                >{ text }</Button>
+                <FormLabel text="Wi-Fi SSID: " label_for="qr-ssid-form" />
+                <FormInput
+                    id="qr-ssid-form"
+                    input_type=InputType::Text
+                    input_size=Size::Small
+                    placeholder="optional, to join Wi-Fi by scanning"
+                    oninput_signal=self.link.callback(|d: InputData| Msg::InputSsid(d.value))
+                />
+            </FormGroup>
+        }
+    }
+
+    pub fn view_qr(&self) -> Html {
+        match self.qr() {
+            Ok(qr) => {
+                let size = qr.svg_size();
+                html! {
+                    <svg
+                        id="qr-code"
+                        class="qr-code"
+                        viewBox=format!("0 0 {0} {0}", size)
+                        shape-rendering="crispEdges"
+                        onclick=self.link.callback(|_| Msg::Hide)
+                    >
+                        <rect width=size.to_string() height=size.to_string() fill="#ffffff" />
+                        <path d=qr.to_svg_path() fill="#000000" />
+                    </svg>
+                }
+            }
+            Err(error) => html! { <p class="qr-error">{ error.to_string() }</p> },
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use crate::password::Reading;
+use wasm_bindgen::JsValue;
+
+/// spoken slower than default, so that listener can write it down
+pub const SPEECH_RATE: f32 = 0.8;
+
+/// return language of voice to read words of reading
+pub fn lang(reading: Reading) -> &'static str {
+    match reading {
+        Reading::Nato => "en-US",
+        Reading::Kana => "ja-JP",
+    }
+}
+
+/// return Web Speech API, which is missing in some browsers
+pub fn speech_synthesis() -> Result<web_sys::SpeechSynthesis, String> {
+    let window = yew::utils::window();
+    match js_sys::Reflect::get(&window, &"speechSynthesis".into()) {
+        Ok(synthesis) if !synthesis.is_undefined() && !synthesis.is_null() => {
+            window.speech_synthesis().map_err(error_message)
+        }
+        _ => Err("Web Speech API is unavailable in this browser".to_string()),
+    }
+}
+
+/// read text aloud in the voice of reading, stopping speech in progress
+pub fn speak(text: &str, reading: Reading) -> Result<(), String> {
+    let synthesis = speech_synthesis()?;
+    let utterance =
+        web_sys::SpeechSynthesisUtterance::new_with_text(text).map_err(error_message)?;
+    utterance.set_lang(lang(reading));
+    utterance.set_rate(SPEECH_RATE);
+    synthesis.cancel();
+    synthesis.speak(&utterance);
+    Ok(())
+}
+
+/// stop speech in progress, such as when password is regenerated
+pub fn cancel() {
+    if let Ok(synthesis) = speech_synthesis() {
+        synthesis.cancel();
+    }
+}
+
+fn error_message(error: JsValue) -> String {
+    format!("{:?}", error)
+}
@@ -0,0 +1,234 @@
+use crate::password::{Encoding, Password, TokenGenerator, TokenSize, TOKEN_DEFAULT_BYTES};
+use yew::prelude::*;
+use yew_styles::{
+    button::Button,
+    card::Card,
+    forms::{
+        form_group::{FormGroup, Orientation},
+        form_input::{FormInput, InputType},
+        form_label::FormLabel,
+    },
+    layouts::{
+        container::{Container, Direction, JustifyContent, Mode, Wrap},
+        item::{AlignSelf, Item, ItemLayout},
+    },
+    styles::{Palette, Size, Style},
+    text::{Text, TextType},
+};
+
+/// min bytes of token in the range input
+pub const TOKEN_PANE_MIN_BYTES: usize = 8;
+
+/// max bytes of token in the range input, larger token can be generated by cli
+pub const TOKEN_PANE_MAX_BYTES: usize = 128;
+
+pub struct TokenPane {
+    link: ComponentLink<Self>,
+    generator: TokenGenerator,
+    token: Password,
+}
+
+pub enum Msg {
+    Generate,
+    EditBytes(usize),
+    SelectEncoding(Encoding),
+    TogglePadding,
+    CopyToken,
+}
+
+impl Component for TokenPane {
+    type Message = Msg;
+    type Properties = ();
+    fn create(_props: Self::Properties, link: ComponentLink<Self>) -> Self {
+        let generator = TokenGenerator::default();
+        let token = generator.generate_token().expect("default generator should generate token.");
+        Self { link, generator, token }
+    }
+
+    fn update(&mut self, msg: Self::Message) -> ShouldRender {
+        match msg {
+            Msg::Generate => (),
+            Msg::EditBytes(bytes) => self.generator.size = TokenSize::Bytes(bytes),
+            Msg::SelectEncoding(encoding) => self.generator.encoding = encoding,
+            Msg::TogglePadding => self.generator.padding = !self.generator.padding,
+            Msg::CopyToken => {
+                self.copy_token_to_clipboard();
+                return false; // do not refresh token
+            }
+        }
+        self.refresh_token()
+    }
+
+    fn change(&mut self, _props: Self::Properties) -> ShouldRender {
+        false
+    }
+
+    fn view(&self) -> Html {
+        self.view_main()
+    }
+}
+
+impl TokenPane {
+    pub fn refresh_token(&mut self) -> ShouldRender {
+        match self.generator.generate_token() {
+            Ok(token) => self.token = token,
+            Err(error) => web_sys::console::log_2(
+                &error.to_string().into(),
+                &format!("invalid setting: {:?}", self.generator).into(),
+            ),
+        }
+        true
+    }
+
+    pub fn copy_token_to_clipboard(&self) {
+        let token = self.token.to_string();
+        let task = async move {
+            let promise = yew::utils::window().navigator().clipboard().write_text(&token);
+            let _result = wasm_bindgen_futures::JsFuture::from(promise).await;
+        };
+        wasm_bindgen_futures::spawn_local(task);
+    }
+
+    pub fn bytes(&self) -> usize {
+        match self.generator.size {
+            TokenSize::Bytes(bytes) => bytes,
+            TokenSize::Bits(bits) => bits.div_ceil(8),
+        }
+    }
+
+    pub fn view_main(&self) -> Html {
+        html! {
+            <Container direction=Direction::Column wrap=Wrap::Wrap justify_content=JustifyContent::Center(Mode::NoMode)>
+                <Item class_name="fix-width" layouts=vec![ItemLayout::ItXs(12)]>
+                    { self.view_generated_token() }
+                </Item>
+                <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                    { self.view_token_setting_pane() }
+                </Item>
+            </Container>
+        }
+    }
+
+    pub fn view_generated_token(&self) -> Html {
+        html! {
+            <div title="Click to copy token!">
+                <Card
+                    card_size=Size::Medium
+                    card_palette=Palette::Link
+                    card_style=Style::Light
+                    onclick_signal=self.link.callback(|_| Msg::CopyToken)
+                    header=Some(html!{
+                        <b>{ format!("Generated Token ({} bits)", self.generator.entropy()) }</b>
+                    })
+                    body=Some(html!{
+                        <p id="token-display">
+                            <Text
+                                text_type=TextType::Plain
+                                text_size=Size::Medium
+                                plain_text=self.token.to_string()
+                                html_text=None
+                            />
+                        </p>
+                    })
+                />
+            </div>
+        }
+    }
+
+    pub fn view_token_setting_pane(&self) -> Html {
+        html! {
+            <Card
+                card_size=Size::Small
+                card_palette=Palette::Primary
+                card_style=Style::Light
+                interaction_effect=false
+                single_content=Some(html!{
+                    <Container direction=Direction::Column wrap=Wrap::Wrap>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::FlexEnd>
+                            <Button
+                                id="generate-token-button"
+                                onclick_signal=self.link.callback(|_| Msg::Generate)
+                                button_palette=Palette::Success
+                                button_style=Style::Light
+                                button_size=Size::Small
+                            >{ "Generate Token!" }</Button>
+                        </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            { self.view_bytes_bar() }
+                        </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            { self.view_encoding_buttons() }
+                        </Item>
+                        <Item layouts=vec![ItemLayout::ItXs(12)] align_self=AlignSelf::Stretch>
+                            { self.view_padding_checkbox() }
+                        </Item>
+                    </Container>
+                })
+            />
+        }
+    }
+
+    pub fn view_bytes_bar(&self) -> Html {
+        let oninput = self.link.callback(|d: InputData| {
+            Msg::EditBytes(d.value.parse().unwrap_or(TOKEN_DEFAULT_BYTES))
+        });
+        html! {
+            <FormGroup orientation=Orientation::Horizontal>
+                <FormInput
+                    id="token-bytes"
+                    input_type=InputType::Range
+                    input_size=Size::Big
+                    min=TOKEN_PANE_MIN_BYTES as u16
+                    max=TOKEN_PANE_MAX_BYTES as u16
+                    oninput_signal=oninput
+                />
+                <FormLabel
+                  text=format!("{} bytes", self.bytes())
+                  label_for="token-bytes"
+                />
+            </FormGroup>
+        }
+    }
+
+    pub fn view_encoding_buttons(&self) -> Html {
+        let buttons: Html = Encoding::ALL
+            .iter()
+            .map(|&encoding| {
+                html! {
+                    <Button
+                        id=format!("encoding-{}-button", encoding.name())
+                        onclick_signal=self.link.callback(move |_| Msg::SelectEncoding(encoding))
+                        button_palette=if encoding == self.generator.encoding { Palette::Success } else { Palette::Standard }
+                        button_style=Style::Outline
+                        button_size=Size::Small
+                    >{ encoding.name() }</Button>
+                }
+            })
+            .collect();
+        html! {
+            <div id="token-encoding" title="Encoding of random bytes">
+                { buttons }
+            </div>
+        }
+    }
+
+    pub fn view_padding_checkbox(&self) -> Html {
+        let onclick = self.link.callback(|_| Msg::TogglePadding);
+        html! {
+            <FormGroup orientation=Orientation::Horizontal>
+                <FormLabel
+                    text="Padding"
+                    label_for="padding-checkbox"
+                />
+                <FormInput
+                    id="padding-checkbox"
+                    input_type=InputType::Checkbox
+                    input_size=Size::Medium
+                    oninput_signal=onclick
+                    checked=self.generator.padding
+                    disabled=!self.generator.encoding.has_padding()
+                />
+            </FormGroup>
+        }
+    }
+}
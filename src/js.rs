@@ -0,0 +1,251 @@
+//! JavaScript API of passgen, which can be used as library without the UI.
+
+use js_sys::{Array, Error, Object, Reflect};
+use wasm_bindgen::{prelude::*, JsCast};
+
+use crate::password::{
+    error_kind,
+    filter::WordFilter,
+    generator,
+    keyboard::{KeyboardLayout, KeyboardSetting, KEYBOARD_DEFAULT_MIN_ENTROPY},
+    password::{LengthUnit, Password},
+    symbol::{self, MarkPreset, MarkSet, UnicodeClass},
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_TYPES: &str = r#"
+/** error of generator, `kind` is the name of error such as "TooShortLength" */
+export interface GeneratorError {
+    kind:
+        | "EmptyLength"
+        | "TooShortLength"
+        | "TooLongLength"
+        | "EmptySymbol"
+        | "TooManyRedraw"
+        | "LowEntropy"
+        | "TooFewSwitches"
+        | "InvalidChar"
+        | "KeyboardWithBytes"
+        | "TooManyClasses"
+        | "Unknown";
+    message: string;
+}
+
+/** class of character in password, which is used for coloring */
+export type CharClass = "lower" | "upper" | "numeric" | "mark" | "addition";
+"#;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(typescript_type = "GeneratorError | undefined")]
+    pub type MaybeGeneratorError;
+
+    #[wasm_bindgen(typescript_type = "CharClass[]")]
+    pub type CharClasses;
+}
+
+/// convert error into javascript `Error`, whose name is kind of error
+fn to_js_error(err: anyhow::Error) -> JsValue {
+    let error = Error::new(&err.to_string());
+    error.set_name(error_kind(&err));
+    error.into()
+}
+
+/// Password generator, whose settings are same as passgen cli.
+#[wasm_bindgen(js_name = PasswordGenerator)]
+pub struct JsPasswordGenerator {
+    generator: generator::PasswordGenerator,
+}
+
+impl Default for JsPasswordGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen(js_class = PasswordGenerator)]
+impl JsPasswordGenerator {
+    /// generator with default settings
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { generator: generator::PasswordGenerator::default() }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn len(&self) -> usize {
+        self.generator.len
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_len(&mut self, len: usize) {
+        self.generator.len = len
+    }
+
+    #[wasm_bindgen(getter, js_name = useLower)]
+    pub fn use_lower(&self) -> bool {
+        self.generator.use_lower
+    }
+
+    #[wasm_bindgen(setter, js_name = useLower)]
+    pub fn set_use_lower(&mut self, use_lower: bool) {
+        self.generator.use_lower = use_lower
+    }
+
+    #[wasm_bindgen(getter, js_name = useUpper)]
+    pub fn use_upper(&self) -> bool {
+        self.generator.use_upper
+    }
+
+    #[wasm_bindgen(setter, js_name = useUpper)]
+    pub fn set_use_upper(&mut self, use_upper: bool) {
+        self.generator.use_upper = use_upper
+    }
+
+    #[wasm_bindgen(getter, js_name = useNumeric)]
+    pub fn use_numeric(&self) -> bool {
+        self.generator.use_numeric
+    }
+
+    #[wasm_bindgen(setter, js_name = useNumeric)]
+    pub fn set_use_numeric(&mut self, use_numeric: bool) {
+        self.generator.use_numeric = use_numeric
+    }
+
+    /// marks such as "!@#", throw if it contains character which cannot be used as mark
+    #[wasm_bindgen(getter)]
+    pub fn mark(&self) -> String {
+        self.generator.mark.iter().collect()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_mark(&mut self, marks: &str) -> Result<(), JsError> {
+        let mut mark = MarkSet::new();
+        for c in marks.chars() {
+            if !mark.contains(&c) && !mark.insert(c) {
+                return Err(JsError::new(&format!("{:?} cannot be used as mark", c)));
+            }
+        }
+        self.generator.mark = mark;
+        Ok(())
+    }
+
+    /// set marks safe in context such as "shell"
+    #[wasm_bindgen(js_name = setMarkPreset)]
+    pub fn set_mark_preset(&mut self, preset: &str) -> Result<(), JsError> {
+        self.generator.mark = MarkSet::from_preset(preset.parse::<MarkPreset>()?);
+        Ok(())
+    }
+
+    /// custom characters, which are NFC normalized
+    #[wasm_bindgen(getter)]
+    pub fn addition(&self) -> String {
+        self.generator.addition.iter().collect()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_addition(&mut self, addition: &str) {
+        self.generator.addition = symbol::normalized_chars(addition)
+    }
+
+    /// strings such as username, whose tokens must not appear in password
+    #[wasm_bindgen(getter)]
+    pub fn context(&self) -> Vec<String> {
+        self.generator.context.clone()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_context(&mut self, context: Vec<String>) {
+        self.generator.context = context
+    }
+
+    /// unicode classes such as "greek", which password uses too
+    #[wasm_bindgen(getter)]
+    pub fn unicode(&self) -> Vec<String> {
+        self.generator.unicode.iter().map(|class| class.name().to_string()).collect()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_unicode(&mut self, unicode: Vec<String>) -> Result<(), JsError> {
+        self.generator.unicode =
+            unicode.iter().map(|class| class.parse::<UnicodeClass>()).collect::<Result<_, _>>()?;
+        Ok(())
+    }
+
+    /// unit of password length, "chars", "graphemes" or "bytes"
+    #[wasm_bindgen(getter)]
+    pub fn unit(&self) -> String {
+        self.generator.unit.name().to_string()
+    }
+
+    #[wasm_bindgen(setter)]
+    pub fn set_unit(&mut self, unit: &str) -> Result<(), JsError> {
+        self.generator.unit = unit.parse::<LengthUnit>()?;
+        Ok(())
+    }
+
+    /// set word filter, with bundled word lists or not, and additional words
+    #[wasm_bindgen(js_name = setFilter)]
+    pub fn set_filter(&mut self, words: Vec<String>, bundled: bool) {
+        let mut filter = if bundled { WordFilter::bundled() } else { WordFilter::new() };
+        for word in words.iter() {
+            filter.insert(word);
+        }
+        self.generator.filter = filter;
+    }
+
+    /// set keyboard layout such as "ios", or undefined to ignore layout.
+    /// if max switches is undefined, switches are minimized keeping min entropy
+    #[wasm_bindgen(js_name = setKeyboard)]
+    pub fn set_keyboard(
+        &mut self,
+        layout: Option<String>,
+        max_switches: Option<usize>,
+        min_entropy: Option<f64>,
+    ) -> Result<(), JsError> {
+        self.generator.keyboard = match layout {
+            Some(layout) => Some(KeyboardSetting {
+                layout: layout.parse::<KeyboardLayout>()?,
+                max_switches,
+                min_entropy: min_entropy.unwrap_or(KEYBOARD_DEFAULT_MIN_ENTROPY),
+            }),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// generate password, throw `Error` whose name is kind of `GeneratorError`
+    pub fn generate(&self) -> Result<String, JsValue> {
+        let password = self.generator.generate_password().map_err(to_js_error)?;
+        Ok(password.to_string())
+    }
+
+    /// return undefined if password can be generated with current settings, or its error
+    #[wasm_bindgen(js_name = canGenerate)]
+    pub fn can_generate(&self) -> MaybeGeneratorError {
+        match self.generator.can_generate() {
+            Ok(()) => JsValue::UNDEFINED.unchecked_into(),
+            Err(err) => {
+                let error = Object::new();
+                Reflect::set(&error, &"kind".into(), &error_kind(&err).into()).unwrap_throw();
+                Reflect::set(&error, &"message".into(), &err.to_string().into()).unwrap_throw();
+                error.unchecked_into()
+            }
+        }
+    }
+
+    /// return true if password satisfies settings, such as numeric is used when useNumeric
+    pub fn validate(&self, password: &str) -> Result<bool, JsValue> {
+        let chars: Vec<char> = password.chars().collect();
+        let password = Password::from_chars(&chars).map_err(to_js_error)?;
+        Ok(self.generator.validate(&password))
+    }
+
+    /// return class of each character in password, such as "lower" or "mark", for coloring
+    pub fn classify(&self, password: &str) -> CharClasses {
+        password
+            .chars()
+            .map(|c| JsValue::from_str(self.generator.classify(&c).name()))
+            .collect::<Array>()
+            .unchecked_into()
+    }
+}
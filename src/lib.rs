@@ -1,5 +1,10 @@
 #![recursion_limit = "1024"]
 
+#[cfg(feature = "web")]
 pub mod components;
-pub mod password;
+#[cfg(feature = "js")]
+pub mod js;
+#[cfg(feature = "web")]
 pub mod start;
+
+pub use passgen_core as password;
@@ -0,0 +1,3 @@
+#!/bin/sh -e
+wasm-pack build --dev --target nodejs --out-name passgen --out-dir ./pkg -- --no-default-features --features js
+node --test tests/js/
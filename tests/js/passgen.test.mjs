@@ -0,0 +1,76 @@
+// test of javascript api, run `./testjs.sh` to build package and run this with node
+import assert from "node:assert/strict";
+import { createRequire } from "node:module";
+import test from "node:test";
+
+const { PasswordGenerator } = createRequire(import.meta.url)("../../pkg/passgen.js");
+
+test("generate password with default settings", () => {
+    const generator = new PasswordGenerator();
+    const password = generator.generate();
+    assert.equal(password.length, 20);
+    assert.match(password, /[a-z]/);
+    assert.match(password, /[A-Z]/);
+    assert.match(password, /[0-9]/);
+    assert.ok(generator.validate(password));
+    assert.equal(generator.canGenerate(), undefined);
+});
+
+test("typed setters", () => {
+    const generator = new PasswordGenerator();
+    generator.len = 8;
+    generator.useUpper = false;
+    generator.useNumeric = false;
+    generator.mark = "";
+    assert.equal(generator.len, 8);
+    assert.equal(generator.useUpper, false);
+    assert.match(generator.generate(), /^[a-z]{8}$/);
+
+    generator.setMarkPreset("url");
+    assert.equal(generator.mark, "-._");
+    generator.addition = "é";
+    assert.equal(generator.addition, "é");
+    generator.context = ["alice", "alice@example.com"];
+    assert.deepEqual(generator.context, ["alice", "alice@example.com"]);
+    generator.unicode = ["greek"];
+    assert.deepEqual(generator.unicode, ["greek"]);
+    generator.unit = "graphemes";
+    generator.setFilter(["hunter"], true);
+    const password = generator.generate();
+    assert.ok(!password.includes("alice"));
+    assert.ok(generator.validate(password));
+
+    assert.throws(() => (generator.mark = "a"));
+    assert.throws(() => (generator.unit = "words"));
+    assert.throws(() => generator.setKeyboard("dvorak"));
+});
+
+test("keyboard layout", () => {
+    const generator = new PasswordGenerator();
+    generator.setKeyboard("ios", 4);
+    assert.ok(generator.validate(generator.generate()));
+    generator.setKeyboard("ios", 0);
+    assert.equal(generator.canGenerate().kind, "TooFewSwitches");
+    generator.setKeyboard(undefined);
+    assert.equal(generator.canGenerate(), undefined);
+});
+
+test("structured error", () => {
+    const generator = new PasswordGenerator();
+    generator.len = 7;
+    assert.deepEqual(generator.canGenerate(), {
+        kind: "TooShortLength",
+        message: "password should be longer than 8, but given is 7",
+    });
+    assert.throws(() => generator.generate(), { name: "TooShortLength" });
+    generator.len = 20;
+    generator.useLower = generator.useUpper = generator.useNumeric = false;
+    generator.mark = "";
+    assert.equal(generator.canGenerate().kind, "EmptySymbol");
+});
+
+test("classify characters for coloring", () => {
+    const generator = new PasswordGenerator();
+    generator.addition = "é";
+    assert.deepEqual(generator.classify("aZ3!é"), ["lower", "upper", "numeric", "mark", "addition"]);
+});